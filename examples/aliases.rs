@@ -0,0 +1,20 @@
+use inline_tweak::tweak as tw;
+use std::time::Duration;
+
+/// A wrapper forwarding to tweak!, as an engine could provide.
+macro_rules! param {
+    ($value:expr) => {
+        inline_tweak::tweak!($value)
+    };
+}
+
+fn main() {
+    loop {
+        // Try changing these values while the application is running
+        let speed = param!(1.5);
+        let count = tw!(10);
+        let scale = tw!(0.5);
+        println!("{} {} {}", speed, count, scale);
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,22 @@
+use inline_tweak::{tweak, watch};
+
+#[inline_tweak::tweak_fn]
+fn opcode(b: u8) -> (u32, u32) {
+    // Literals in match patterns stay as they are, only the arm values are tweakable
+    let k = match b {
+        b'M' => 7,
+        _ => 8,
+    };
+    (k, 100)
+}
+
+fn main() {
+    loop {
+        // Try changing the values while the application is running
+        let magic: &[u8] = tweak!(b"\x89PNG");
+        let separator = tweak!(b';');
+        let name = tweak!(c"inline_tweak");
+        println!("{:?} {} {:?} {:?}", magic, separator, name, opcode(b'M'));
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -0,0 +1,14 @@
+use inline_tweak::{tweak, watch};
+use std::collections::HashMap;
+
+fn main() {
+    loop {
+        let steps: Vec<u32> = tweak!(vec![1, 2, 4, 8]); // Try changing the values while the application is running
+        let speeds: HashMap<&str, f32> = tweak!(HashMap::from([
+            ("walk", 1.0), // Comments are allowed inside of tweaked values
+            ("run", 2.5),
+        ]));
+        println!("{:?} walk:{} run:{}", steps, speeds["walk"], speeds["run"]);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
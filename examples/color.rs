@@ -0,0 +1,11 @@
+use inline_tweak::{tweak, watch, TweakColor};
+
+fn main() {
+    loop {
+        // Try changing the color while the application is running,
+        // e.g to TweakColor::hex("#ff8800cc"), TweakColor::rgb(255, 128, 0) or TweakColor::hsl(30.0, 1.0, 0.5)
+        let color: [u8; 4] = tweak!(TweakColor::hex("#ff8800")).into();
+        println!("{:?}", color);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
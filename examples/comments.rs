@@ -0,0 +1,19 @@
+use inline_tweak::tweak;
+use std::time::Duration;
+
+/// Mentioning `tweak!(1.0)` in a doc comment does not shift the tweaks below.
+fn main() {
+    loop {
+        // Neither does a tweak!(1.0) in a comment,
+        /* a block /* nested tweak!(2.0) */ comment tweak!(3.0), */
+        let s = "a string with tweak!(4.0) and an escaped \" quote";
+        let r = r#"a raw string with "tweak!(5.0)""#;
+        let c = '"';
+
+        // Try changing these values while the application is running
+        let speed = tweak!(1.5);
+        let count = tweak!(10);
+        println!("{} {} {} {} {}", s.len(), r.len(), c, speed, count);
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,13 @@
+use inline_tweak::{tweak, watch};
+
+fn main() {
+    loop {
+        // Try changing the values while the application is running.
+        // The macro can be called with parentheses, brackets or braces.
+        let a = tweak!(1.0);
+        let b = tweak![[2, 3]];
+        let c = tweak! {(4, ")")};
+        println!("{} {:?} {:?}", a, b, c);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
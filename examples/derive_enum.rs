@@ -0,0 +1,26 @@
+use inline_tweak::{tweak, watch, Tweakable};
+
+#[derive(Clone, Tweakable)]
+enum Easing {
+    Linear,
+    Cubic(f32),
+    Bezier { a: f32, b: f32 },
+}
+
+fn ease(easing: &Easing, t: f32) -> f32 {
+    match easing {
+        Easing::Linear => t,
+        Easing::Cubic(k) => t + k * t * (1.0 - t) * (1.0 - 2.0 * t),
+        Easing::Bezier { a, b } => {
+            3.0 * a * t * (1.0 - t).powi(2) + 3.0 * b * t * t * (1.0 - t) + t.powi(3)
+        }
+    }
+}
+
+fn main() {
+    loop {
+        let easing = tweak!(Easing::Cubic(0.3)); // Try changing it to Easing::Linear while the application is running
+        println!("{}", ease(&easing, 0.25));
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -0,0 +1,19 @@
+use inline_tweak::{tweak, watch, Tweakable};
+
+#[derive(Clone, Tweakable)]
+struct Vec2 {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Clone, Tweakable)]
+struct Meters(f32);
+
+fn main() {
+    loop {
+        let pos = tweak!(Vec2 { x: 1.0, y: 2.0 }); // Try changing the values while the application is running
+        let dist = tweak!(Meters(3.5));
+        println!("x:{} y:{} dist:{}", pos.x, pos.y, dist.0);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -0,0 +1,10 @@
+use inline_tweak::tweak;
+use std::time::Duration;
+
+fn main() {
+    loop {
+        println!("tick");
+        // Try changing the duration while the application is running, e.g to Duration::from_secs_f32(0.5)
+        std::thread::sleep(tweak!(Duration::from_millis(200)));
+    }
+}
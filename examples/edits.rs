@@ -0,0 +1,15 @@
+use inline_tweak::tweak;
+use std::time::Duration;
+
+fn main() {
+    loop {
+        // Try adding a tweak while the application is running, e.g `let depth = tweak!(25);` here,
+        // or removing one. The other tweaks keep their own values, and a removed tweak keeps its last value.
+        // A tweak whose code changed on both sides, or which looks the same as a new one, keeps its last value too.
+        let width = tweak!(100);
+        let height = tweak!(50);
+        let speed = tweak!(1.5);
+        println!("{}x{} at {}", width, height, speed);
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,22 @@
+use inline_tweak::{tweak, tweakable_flags, watch};
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug)]
+    struct DebugDraw: u32 {
+        const WIREFRAME = 1;
+        const NORMALS = 1 << 1;
+        const BOUNDS = 1 << 2;
+    }
+}
+
+tweakable_flags!(DebugDraw { WIREFRAME, NORMALS, BOUNDS, empty(), all() });
+
+fn main() {
+    loop {
+        // Try changing the flags while the application is running,
+        // e.g to DebugDraw::BOUNDS, DebugDraw::all() or DebugDraw::empty()
+        let flags = tweak!(DebugDraw::WIREFRAME | DebugDraw::NORMALS);
+        println!("{:?}", flags);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -0,0 +1,10 @@
+use inline_tweak::{tweak, watch, TweakFromStr};
+use std::net::IpAddr;
+
+fn main() {
+    loop {
+        let addr: IpAddr = tweak!(TweakFromStr::new("127.0.0.1").unwrap()).0; // Try changing the address while the application is running
+        println!("{}", addr);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
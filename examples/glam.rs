@@ -0,0 +1,13 @@
+use glam::{vec3, Quat, Vec3};
+use inline_tweak::{tweak, watch};
+
+fn main() {
+    loop {
+        // Try changing the position or the rotation while the application is running,
+        // e.g to Vec3::new(0.0, 5.0, 0.0), Vec3::splat(2.0) or Quat::IDENTITY
+        let position: Vec3 = tweak!(vec3(1.0, 2.0, 3.0));
+        let rotation: Quat = tweak!(Quat::from_rotation_y(0.5));
+        println!("{} {}", position, rotation * Vec3::X);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -0,0 +1,12 @@
+use inline_tweak::{tweak, watch};
+
+fn main() {
+    loop {
+        // Try changing the value to None while the application is running
+        match tweak!(Some(3.0)) {
+            Some(speed) => println!("speed boost: {}", speed),
+            None => println!("no speed boost"),
+        }
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -0,0 +1,11 @@
+use inline_tweak::{tweak, watch};
+
+fn main() {
+    loop {
+        // Try changing the ranges while the application is running, e.g to 0.5..=2.0 or 10..
+        let spawn_delay = tweak!(0.0..1.0);
+        let enemies = tweak!(3..=7);
+        println!("{:?} {:?}", spawn_delay, enemies);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
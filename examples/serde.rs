@@ -0,0 +1,34 @@
+use inline_tweak::{tweak, watch, TweakSerde};
+use serde::Deserialize;
+
+#[derive(Clone, Deserialize)]
+enum Difficulty {
+    Easy,
+    Hard { enemies: u32 },
+}
+
+#[derive(Clone, Deserialize)]
+struct Config {
+    speed: f32,
+    names: Vec<String>,
+    difficulty: Difficulty,
+}
+
+fn main() {
+    loop {
+        // Try changing the config while the application is running
+        let config = tweak!(TweakSerde(Config {
+            speed: 2.0,
+            names: vec!["walk".to_string(), "run".to_string()],
+            difficulty: Difficulty::Hard { enemies: 3 },
+        }))
+        .0;
+
+        let enemies = match config.difficulty {
+            Difficulty::Easy => 0,
+            Difficulty::Hard { enemies } => enemies,
+        };
+        println!("{} {:?} {}", config.speed, config.names, enemies);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -3,7 +3,15 @@ use inline_tweak::{tweak, watch};
 fn main() {
     loop {
         println!("{}", tweak!("Lorem ipsum")); // Try changing the text while the application is running
-        println!("{}", tweak!("габарит не выбран")); // Supports non ascii text
+        println!("{} {}", tweak!("габарит"), tweak!("не выбран")); // Supports non ascii text, also before a tweak
+        println!("{} {}", tweak!("smile :)"), tweak!(';')); // Parentheses and semicolons in literals are fine
+        println!(
+            "{}",
+            tweak!(
+                r#"a multi-line
+raw string; with "quotes" )"#
+            )
+        );
         watch!(); // The thread will sleep here until anything in the file changes
     }
 }
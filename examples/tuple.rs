@@ -0,0 +1,10 @@
+use inline_tweak::{tweak, watch};
+
+fn main() {
+    loop {
+        let color: [f32; 4] = tweak!([0.2, 0.4, 0.6, 1.0]); // Try changing the values while the application is running
+        let (offset, scales) = tweak!(((1.0, -2.0), [0.5; 3])); // Tuples and arrays can be nested
+        println!("{:?} {:?} {:?}", color, offset, scales);
+        watch!(); // The thread will sleep here until anything in the file changes
+    }
+}
@@ -1,18 +1,23 @@
 extern crate proc_macro;
+mod tweakable;
+
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::ToTokens;
+use syn::meta::ParseNestedMeta;
 use syn::punctuated::Punctuated;
 use syn::visit_mut::VisitMut;
 use syn::{
-    parse_macro_input, Attribute, Expr, ExprBreak, ExprConst, ExprMacro, ItemConst, ItemStatic,
-    Lit, LitInt, LitStr, Macro, MacroDelimiter, Path, PathSegment, Token, Type,
+    parse_macro_input, Attribute, DeriveInput, Expr, ExprBreak, ExprConst, ExprMacro, ItemConst,
+    ItemStatic, Lit, LitInt, LitStr, Macro, MacroDelimiter, Pat, Path, Token, Type,
 };
 
 struct LiteralReplacer {
     nth: usize,
     fname: Ident,
     release_tweak: bool,
+    /// The path to the `inline_tweak` crate.
+    krate: Path,
 }
 
 impl LiteralReplacer {
@@ -27,25 +32,17 @@ impl LiteralReplacer {
             }),
         );
 
+        let krate = &self.krate;
+        let path: Path = if self.release_tweak {
+            syn::parse_quote!(#krate::derive_release_tweak)
+        } else {
+            syn::parse_quote!(#krate::derive_tweak)
+        };
+
         *i = Expr::Macro(ExprMacro {
             attrs: vec![],
             mac: Macro {
-                path: Path {
-                    segments: [
-                        PathSegment::from(Ident::new("inline_tweak", Span::call_site())),
-                        PathSegment::from(Ident::new(
-                            if self.release_tweak {
-                                "derive_release_tweak"
-                            } else {
-                                "derive_tweak"
-                            },
-                            Span::call_site(),
-                        )),
-                    ]
-                    .into_iter()
-                    .collect(),
-                    leading_colon: Some(Default::default()),
-                },
+                path,
                 bang_token: Default::default(),
                 delimiter: MacroDelimiter::Paren(Default::default()),
                 tokens: [
@@ -73,7 +70,15 @@ impl VisitMut for LiteralReplacer {
     fn visit_expr_mut(&mut self, i: &mut Expr) {
         match *i {
             Expr::Lit(syn::ExprLit {
-                lit: Lit::Char(_) | Lit::Int(_) | Lit::Float(_) | Lit::Bool(_) | Lit::Str(_),
+                lit:
+                    Lit::Char(_)
+                    | Lit::Int(_)
+                    | Lit::Float(_)
+                    | Lit::Bool(_)
+                    | Lit::Str(_)
+                    | Lit::Byte(_)
+                    | Lit::ByteStr(_)
+                    | Lit::CStr(_),
                 ..
             }) => {
                 self.replace(i);
@@ -102,14 +107,19 @@ impl VisitMut for LiteralReplacer {
 
     fn visit_item_static_mut(&mut self, _: &mut ItemStatic) {}
 
+    /// Patterns only accept literals, not the expressions they would be replaced by.
+    fn visit_pat_mut(&mut self, _: &mut Pat) {}
+
     fn visit_type_mut(&mut self, _: &mut Type) {}
 
     fn visit_attribute_mut(&mut self, _: &mut Attribute) {}
 }
 
-/// Makes all the number/bool/char literals in a function tweakable.  
+/// Makes all the number/bool/char/string/byte literals in a function tweakable.  
 /// Doesn't apply to literals in macros as they cannot be replaced by expressions reliably. (e.g in calls to println!)
 ///
+/// If `inline_tweak` is renamed or re-exported, its path can be given as `#[tweak_fn(crate = path::to::inline_tweak)]`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -124,18 +134,21 @@ impl VisitMut for LiteralReplacer {
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn tweak_fn(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    do_fn(item, false)
+pub fn tweak_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
+    do_fn(attr, item, false)
 }
 
-/// Makes all the number/bool/char literals in a function tweakable.  
+/// Makes all the number/bool/char/string/byte literals in a function tweakable,
+/// in release mode too when the `release_tweak` feature of `inline_tweak` is enabled.  
 /// Doesn't apply to literals in macros as they cannot be replaced by expressions reliably. (e.g in calls to println!)
 ///
+/// If `inline_tweak` is renamed or re-exported, its path can be given as `#[release_tweak_fn(crate = path::to::inline_tweak)]`.
+///
 /// # Examples
 ///
 /// ```rust
 /// # use std::time::Duration;
-/// #[inline_tweak::tweak_fn]
+/// #[inline_tweak::release_tweak_fn]
 /// fn main() {
 ///     loop {
 ///         let v = 1.0; // Try changing this value!
@@ -143,13 +156,17 @@ pub fn tweak_fn(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///         std::thread::sleep(Duration::from_millis(200)); // or even this value :)
 ///     }
 /// }
-/// ```#[cfg(feature = "release_tweak")]
+/// ```
 #[proc_macro_attribute]
-pub fn release_tweak_fn(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    do_fn(item, true)
+pub fn release_tweak_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
+    do_fn(attr, item, true)
 }
 
-fn do_fn(item: TokenStream, release_tweak: bool) -> TokenStream {
+fn do_fn(attr: TokenStream, item: TokenStream, release_tweak: bool) -> TokenStream {
+    let mut krate = None;
+    let parser = syn::meta::parser(crate_path_parser(&mut krate));
+    parse_macro_input!(attr with parser);
+
     let mut v: syn::ItemFn = parse_macro_input!(item as syn::ItemFn);
 
     let fname = v.sig.ident.clone();
@@ -158,8 +175,63 @@ fn do_fn(item: TokenStream, release_tweak: bool) -> TokenStream {
         nth: 0,
         fname,
         release_tweak,
+        krate: krate.unwrap_or_else(default_crate_path),
     }
     .visit_item_fn_mut(&mut v);
 
     v.into_token_stream().into()
 }
+
+/// The path to `inline_tweak` when it is not given.
+fn default_crate_path() -> Path {
+    syn::parse_quote!(::inline_tweak)
+}
+
+/// Parses the `crate = path` argument, giving the path to `inline_tweak` when the dependency is renamed
+/// or only reachable through the re-export of another crate.
+fn crate_path_parser(
+    krate: &mut Option<Path>,
+) -> impl FnMut(ParseNestedMeta) -> syn::Result<()> + '_ {
+    move |meta| {
+        if meta.path.is_ident("crate") {
+            *krate = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported argument, expected `crate = path`"))
+        }
+    }
+}
+
+/// Implements `Tweakable` for a struct or an enum, so that it can be tweaked from its literal syntax.
+/// Every field is parsed using its own `Tweakable` implementation.
+/// Enum variants can be written as `Variant`, `Enum::Variant` or with their full path.
+///
+/// If `inline_tweak` is renamed or re-exported, its path can be given as `#[inline_tweak(crate = path::to::inline_tweak)]`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(Clone, inline_tweak::Tweakable)]
+/// struct Vec2 {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Clone, inline_tweak::Tweakable)]
+/// struct Meters(f32);
+///
+/// #[derive(Clone, inline_tweak::Tweakable)]
+/// enum Easing {
+///     Linear,
+///     Cubic(f32),
+/// }
+///
+/// let pos = inline_tweak::tweak!(Vec2 { x: 1.0, y: 2.0 });
+/// let dist = inline_tweak::tweak!(Meters(3.5));
+/// let easing = inline_tweak::tweak!(Easing::Cubic(0.3));
+/// ```
+#[proc_macro_derive(Tweakable, attributes(inline_tweak))]
+pub fn derive_tweakable(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    tweakable::derive_tweakable(input).into()
+}
@@ -0,0 +1,145 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Ident, Path};
+
+/// Generates the `Tweakable` implementation of a struct or enum, parsing its literal syntax.
+/// The path to `inline_tweak` can be given with `#[inline_tweak(crate = path)]`.
+pub(crate) fn derive_tweakable(mut input: DeriveInput) -> TokenStream {
+    let mut krate = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("inline_tweak"))
+    {
+        if let Err(e) = attr.parse_nested_meta(crate::crate_path_parser(&mut krate)) {
+            return e.to_compile_error();
+        }
+    }
+    let krate = krate.unwrap_or_else(crate::default_crate_path);
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(parse_quote!(#krate::Tweakable));
+        }
+    }
+
+    let name = &input.ident;
+    let ty = name.unraw().to_string();
+    let result = quote!(::core::result::Result<::core::option::Option<Self>, #krate::TweakError>);
+
+    let body = match &input.data {
+        Data::Struct(s) => parse_fields(&krate, &ty, None, &s.fields),
+        Data::Enum(e) => {
+            let variants = e.variants.iter().map(|variant| {
+                let parse_variant =
+                    parse_fields(&krate, &ty, Some(&variant.ident), &variant.fields);
+                quote! {
+                    let parsed = (|| -> #result { #parse_variant })()?;
+                    if parsed.is_some() {
+                        return ::core::result::Result::Ok(parsed);
+                    }
+                }
+            });
+
+            quote! {
+                #(#variants)*
+                ::core::result::Result::Ok(::core::option::Option::None)
+            }
+        }
+        Data::Union(_) => {
+            return Error::new_spanned(name, "Tweakable cannot be derived for unions")
+                .to_compile_error();
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #krate::Tweakable for #name #ty_generics #where_clause {
+            fn parse(x: &str) -> ::core::option::Option<Self> {
+                <Self as #krate::Tweakable>::try_parse(x).ok().flatten()
+            }
+
+            fn try_parse(x: &str) -> #result {
+                #body
+            }
+        }
+    }
+}
+
+/// Unwraps an `Option` in the generated `try_parse`, returning `Ok(None)` if it is `None`.
+fn try_some(option: TokenStream) -> TokenStream {
+    quote! {
+        match #option {
+            ::core::option::Option::Some(v) => v,
+            ::core::option::Option::None => {
+                return ::core::result::Result::Ok(::core::option::Option::None)
+            }
+        }
+    }
+}
+
+/// Generates the parsing of `x` as the struct `ty` or its enum `variant`, returning `Result<Option<Self>, TweakError>`.
+/// The errors of the fields are forwarded.
+fn parse_fields(krate: &Path, ty: &str, variant: Option<&Ident>, fields: &Fields) -> TokenStream {
+    let helpers = quote!(#krate::derive_helpers);
+    let parse = |field: TokenStream| try_some(quote!(#krate::Tweakable::try_parse(#field)?));
+
+    let (constructor, variant_name) = match variant {
+        Some(variant) => {
+            let name = variant.unraw().to_string();
+            (
+                quote!(Self::#variant),
+                quote!(::core::option::Option::Some(#name)),
+            )
+        }
+        None => (quote!(Self), quote!(::core::option::Option::None)),
+    };
+
+    match fields {
+        Fields::Named(named) => {
+            let n_fields = named.named.len();
+            let idents = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            let values = idents.clone().map(|ident| {
+                let name = ident.unraw().to_string();
+                parse(try_some(quote!(#helpers::field(&fields, #name))))
+            });
+            let fields = try_some(quote!(#helpers::named_fields(x, #ty, #variant_name)));
+
+            quote! {
+                let fields = #fields;
+                if fields.len() != #n_fields {
+                    return ::core::result::Result::Ok(::core::option::Option::None);
+                }
+                ::core::result::Result::Ok(::core::option::Option::Some(#constructor {
+                    #(#idents: #values,)*
+                }))
+            }
+        }
+        Fields::Unnamed(unnamed) => {
+            let n_fields = unnamed.unnamed.len();
+            let values = (0..n_fields).map(|i| parse(quote!(fields[#i])));
+
+            // Newtypes can also be written as their inner value
+            let fallback = if n_fields == 1 && variant.is_none() {
+                quote!(.or_else(|| ::core::option::Option::Some(::std::vec![x])))
+            } else {
+                quote!()
+            };
+
+            let fields = try_some(quote!(#helpers::tuple_fields(x, #ty, #variant_name)#fallback));
+
+            quote! {
+                let fields = #fields;
+                if fields.len() != #n_fields {
+                    return ::core::result::Result::Ok(::core::option::Option::None);
+                }
+                ::core::result::Result::Ok(::core::option::Option::Some(#constructor(#(#values,)*)))
+            }
+        }
+        Fields::Unit => quote! {
+            ::core::result::Result::Ok(#helpers::is_unit(x, #ty, #variant_name).then_some(#constructor))
+        },
+    }
+}
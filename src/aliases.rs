@@ -0,0 +1,172 @@
+//! The names of the macros and attributes which forward to the ones of this crate,
+//! so that their calls are found in the source files too.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The registered names of macros forwarding to `tweak!` or `release_tweak!`.
+static TWEAK_MACROS: Names = Names::new();
+
+/// The registered names of attributes forwarding to `#[tweak_fn]` or `#[release_tweak_fn]`.
+static TWEAK_FN_ATTRIBUTES: Names = Names::new();
+
+/// A list of registered names. It only ever grows, so its length tells whether names were registered
+/// since it was last read, without locking it.
+struct Names {
+    names: Mutex<Vec<String>>,
+    len: AtomicUsize,
+}
+
+impl Names {
+    const fn new() -> Self {
+        Names {
+            names: Mutex::new(Vec::new()),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    fn register(&self, name: &str) {
+        let mut names = self.names.lock().unwrap();
+        if !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+            self.len.store(names.len(), Ordering::Release);
+        }
+    }
+
+    #[allow(dead_code)]
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    #[allow(dead_code)]
+    fn to_vec(&self) -> Vec<String> {
+        self.names.lock().unwrap().clone()
+    }
+}
+
+/// Registers the name of a macro which expands to `tweak!` or `release_tweak!` with the same arguments,
+/// like a wrapper `param!` from another crate, or a renamed import `use inline_tweak::tweak as tw;`.
+/// Its calls are then hot-reloaded like the ones of `tweak!`.
+///
+/// Wrappers are also learned by themselves in each file where one of their calls is evaluated,
+/// registering them up front only saves indexing these files again.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// macro_rules! param {
+///     ($value:expr) => {
+///         inline_tweak::tweak!($value)
+///     };
+/// }
+///
+/// inline_tweak::register_tweak_macro("param");
+/// loop {
+///     println!("{}", param!(3.14));
+/// }
+/// ```
+pub fn register_tweak_macro(name: &str) {
+    TWEAK_MACROS.register(name);
+}
+
+/// Registers the name of an attribute which expands to `#[tweak_fn]` or `#[release_tweak_fn]`,
+/// like a re-export under another name, so that the literals of the functions it is applied to are found.
+///
+/// Renamed imports in the same file, like `use inline_tweak::tweak_fn as tweakable;`, are found by themselves.
+pub fn register_tweak_fn_attribute(name: &str) {
+    TWEAK_FN_ATTRIBUTES.register(name);
+}
+
+/// The macros of std and of this crate, which never forward to `tweak!` and are not learned as doing so.
+#[cfg(all(
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+const NOT_LEARNABLE_MACROS: &[&str] = &[
+    "tweak",
+    "release_tweak",
+    "derive_tweak",
+    "derive_release_tweak",
+    "watch",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "cfg",
+    "column",
+    "compile_error",
+    "concat",
+    "dbg",
+    "env",
+    "eprint",
+    "eprintln",
+    "file",
+    "format",
+    "format_args",
+    "include",
+    "include_bytes",
+    "include_str",
+    "line",
+    "matches",
+    "module_path",
+    "option_env",
+    "panic",
+    "print",
+    "println",
+    "stringify",
+    "thread_local",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "vec",
+    "write",
+    "writeln",
+];
+
+/// Returns whether `name` may be learned as a macro forwarding to `tweak!`.
+#[cfg(all(
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn is_learnable_macro(name: &str) -> bool {
+    !NOT_LEARNABLE_MACROS.contains(&name)
+}
+
+/// Returns the registered names of tweak macros.
+#[cfg(all(
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn tweak_macros() -> Vec<String> {
+    TWEAK_MACROS.to_vec()
+}
+
+/// Returns the number of registered tweak macros, which only changes when one is registered.
+#[cfg(all(
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn tweak_macro_count() -> usize {
+    TWEAK_MACROS.len()
+}
+
+/// Returns the registered names of tweak_fn attributes.
+#[cfg(all(
+    feature = "derive",
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn tweak_fn_attributes() -> Vec<String> {
+    TWEAK_FN_ATTRIBUTES.to_vec()
+}
+
+/// Returns the number of registered tweak_fn attributes, which only changes when one is registered.
+#[cfg(all(
+    feature = "derive",
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn tweak_fn_attribute_count() -> usize {
+    TWEAK_FN_ATTRIBUTES.len()
+}
@@ -0,0 +1,712 @@
+//! A minimal Rust tokenizer, used to find the `tweak!` calls of a source file and to split the tweaked values.
+//!
+//! Comments (including nested block comments) and the contents of string, raw string, byte and char literals
+//! are skipped, so that a `tweak!(` written inside of them is not mistaken for a call,
+//! and a `)` or `;` inside of a tweaked literal does not end it early.
+
+use crate::hasher::FxHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+/// The kinds of tokens. Only what is needed to find the macro calls is distinguished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// An identifier or keyword, possibly raw like `r#type`.
+    Ident,
+    /// A lifetime or a label, like `'a`.
+    Lifetime,
+    /// A string, char, byte or number literal.
+    Literal,
+    /// A single punctuation char, like `!` or `(`. Operators made of several chars like `::` are split.
+    Punct(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    /// The byte range of the token in the source.
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The number of tokens on each side of a call which make up its context.
+const CONTEXT_TOKENS: usize = 4;
+
+/// The number of tokens next to a call which must be unchanged on the side of it that was edited.
+const NEAR_TOKENS: usize = 2;
+
+/// A `tweak!(..)` or `release_tweak!(..)` call found in a source file.
+/// The macro may also be called with brackets or braces, like `tweak![..]` or `tweak!{..}`.
+pub(crate) struct TweakCall {
+    /// The byte index of the start of the macro path, e.g of `inline_tweak` in `inline_tweak::tweak!(..)`.
+    /// This is where `column!()` points to.
+    pub path_start: usize,
+    /// The byte range of the tweaked value, between the opening delimiter and either the closing one
+    /// or the `;` before the default value. It extends to the end of the source if the call is not closed.
+    pub value: Range<usize>,
+    pub context: CallContext,
+}
+
+/// Hashes of the call and of the few tokens before and after it, leaving out the tweaked values.
+/// They are used to find the call again after the code around it was edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CallContext {
+    /// The macro path up to the opening delimiter.
+    call: u64,
+    /// The tokens before the call, nearest first. Missing tokens at the start of the file are 0.
+    before: [u64; CONTEXT_TOKENS],
+    /// The tokens after the closing delimiter, nearest first. Missing tokens at the end of the file are 0.
+    after: [u64; CONTEXT_TOKENS],
+}
+
+impl CallContext {
+    /// Whether `other` may be the same call after code was added or removed next to it:
+    /// the tokens on one side of it are unchanged, and the nearest ones on the other side too.
+    fn is_close(&self, other: &Self) -> bool {
+        let near = |a: &[u64], b: &[u64]| a[..NEAR_TOKENS] == b[..NEAR_TOKENS];
+        self.call == other.call
+            && (self.before == other.before && near(&self.after, &other.after)
+                || self.after == other.after && near(&self.before, &other.before))
+    }
+}
+
+/// Returns the `tweak!(..)` and `release_tweak!(..)` calls of `src`, in order,
+/// along with the calls of the `macros` forwarding to them.
+pub(crate) fn find_tweak_calls(src: &str, macros: &[String]) -> Vec<TweakCall> {
+    let tokens = tokenize(src);
+    let text = |token: &Token| &src[token.start..token.end];
+
+    // The token indices of the path start, the value and the closing delimiter of each call
+    let mut sites: Vec<(usize, Range<usize>, usize)> = vec![];
+    for (i, window) in tokens.windows(3).enumerate() {
+        let [name, bang, open] = window else {
+            unreachable!()
+        };
+        if name.kind != TokenKind::Ident
+            || !is_tweak_macro(text(name), macros)
+            || bang.kind != TokenKind::Punct('!')
+        {
+            continue;
+        }
+        let close = match open.kind {
+            TokenKind::Punct('(') => ')',
+            TokenKind::Punct('[') => ']',
+            TokenKind::Punct('{') => '}',
+            _ => continue,
+        };
+
+        // Go back to the start of the path, made of `::` separated identifiers
+        let mut path_start = i;
+        while path_start >= 2
+            && tokens[path_start - 1].kind == TokenKind::Punct(':')
+            && tokens[path_start - 2].kind == TokenKind::Punct(':')
+        {
+            path_start -= 2;
+            if path_start >= 1 && tokens[path_start - 1].kind == TokenKind::Ident {
+                path_start -= 1;
+            } else {
+                break;
+            }
+        }
+
+        // Find the end of the value and the closing delimiter, skipping over nested brackets
+        let mut depth = 0u32;
+        let (mut value_end, mut close_index) = (None, tokens.len());
+        for (j, token) in tokens.iter().enumerate().skip(i + 3) {
+            match token.kind {
+                TokenKind::Punct('(' | '[' | '{') => depth += 1,
+                TokenKind::Punct(')' | ']' | '}') if depth > 0 => depth -= 1,
+                TokenKind::Punct(c) if depth == 0 && c == close => {
+                    value_end.get_or_insert(j);
+                    close_index = j;
+                    break;
+                }
+                TokenKind::Punct(';') if depth == 0 => {
+                    value_end.get_or_insert(j);
+                }
+                _ => {}
+            }
+        }
+
+        sites.push((
+            path_start,
+            i + 3..value_end.unwrap_or(tokens.len()),
+            close_index,
+        ));
+    }
+
+    // The tweaked values are left out of the contexts, as they are what is expected to change
+    let mut in_value = vec![false; tokens.len()];
+    for (_, value, _) in &sites {
+        in_value[value.clone()].fill(true);
+    }
+
+    let hash = |range: Range<usize>| {
+        let mut hasher = FxHasher::default();
+        for i in range {
+            match in_value[i] {
+                true => hasher.write_u8(0),
+                false => text(&tokens[i]).hash(&mut hasher),
+            }
+        }
+        hasher.finish()
+    };
+    let side = |indices: &mut dyn Iterator<Item = usize>| {
+        let mut hashes = [0; CONTEXT_TOKENS];
+        for (hash_out, i) in hashes.iter_mut().zip(indices) {
+            *hash_out = hash(i..i + 1);
+        }
+        hashes
+    };
+
+    sites
+        .into_iter()
+        .map(|(path_start, value, close_index)| TweakCall {
+            path_start: tokens[path_start].start,
+            value: tokens[value.start - 1].end
+                ..tokens.get(value.end).map_or(src.len(), |token| token.start),
+            context: CallContext {
+                call: hash(path_start..value.start),
+                before: side(&mut (0..path_start).rev()),
+                after: side(&mut (close_index + 1..tokens.len())),
+            },
+        })
+        .collect()
+}
+
+/// Matches the calls found when the file was first parsed to the current ones, from their contexts,
+/// so that adding or removing a tweak does not shift the values of the others.
+/// Returns the index of the current call matching each original one, or `None` if it was removed
+/// or cannot be told apart from other calls, e.g when a line with a tweak was duplicated.
+pub(crate) fn match_calls(original: &[CallContext], current: &[CallContext]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; original.len()];
+    match_between(
+        original,
+        current,
+        0..original.len(),
+        0..current.len(),
+        &mut matches,
+    );
+    matches
+}
+
+/// Matches the calls of `original[orig]` to the ones of `current[cur]`, like a diff.
+///
+/// If the contexts are the same on both sides, as when only values were edited, they are paired in order.
+/// Otherwise the calls which match exactly one call on the other side and the other way around are paired,
+/// preferring unchanged contexts. The calls between them are paired in order if there are as many on both sides,
+/// as when the code next to a call was edited, or matched the same way otherwise.
+fn match_between(
+    original: &[CallContext],
+    current: &[CallContext],
+    orig: Range<usize>,
+    cur: Range<usize>,
+    matches: &mut [Option<usize>],
+) {
+    if orig.is_empty() || cur.is_empty() {
+        return;
+    }
+    if original[orig.clone()] == current[cur.clone()] {
+        for (i, j) in orig.zip(cur) {
+            matches[i] = Some(j);
+        }
+        return;
+    }
+
+    let mut pairs = unique_pairs(original, current, &orig, &cur, |a, b| a == b);
+    if pairs.is_empty() {
+        pairs = unique_pairs(original, current, &orig, &cur, CallContext::is_close);
+    }
+    if pairs.is_empty() {
+        // The calls cannot be told apart, but are all there if there are as many on both sides
+        match_gap(orig, cur, matches);
+        return;
+    }
+
+    let gap = |orig: Range<usize>, cur: Range<usize>, matches: &mut [Option<usize>]| {
+        if orig.len() == cur.len() {
+            match_gap(orig, cur, matches);
+        } else {
+            match_between(original, current, orig, cur, matches);
+        }
+    };
+    let (mut gap_i, mut gap_j) = (orig.start, cur.start);
+    for (i, j) in increasing_pairs(&pairs) {
+        gap(gap_i..i, gap_j..j, matches);
+        matches[i] = Some(j);
+        (gap_i, gap_j) = (i + 1, j + 1);
+    }
+    gap(gap_i..orig.end, gap_j..cur.end, matches);
+}
+
+/// Pairs the calls between two matched ones (or the ends of the file) in order, if there are as many on both sides.
+/// Otherwise some were added or removed, and which ones cannot be told.
+fn match_gap(orig: Range<usize>, cur: Range<usize>, matches: &mut [Option<usize>]) {
+    if orig.len() == cur.len() {
+        for (i, j) in orig.zip(cur) {
+            matches[i] = Some(j);
+        }
+    }
+}
+
+/// Returns the pairs of calls of `original[orig]` and `current[cur]` which are the `same`,
+/// and are not the `same` as any other call on either side.
+fn unique_pairs(
+    original: &[CallContext],
+    current: &[CallContext],
+    orig: &Range<usize>,
+    cur: &Range<usize>,
+    same: impl Fn(&CallContext, &CallContext) -> bool,
+) -> Vec<(usize, usize)> {
+    orig.clone()
+        .filter_map(|i| {
+            let mut candidates = cur.clone().filter(|&j| same(&original[i], &current[j]));
+            let j = candidates.next()?;
+            let unique = candidates.next().is_none()
+                && orig
+                    .clone()
+                    .filter(|&k| same(&original[k], &current[j]))
+                    .count()
+                    == 1;
+            unique.then_some((i, j))
+        })
+        .collect()
+}
+
+/// Returns the longest subsequence of `pairs`, sorted by their first index, whose second index increases too.
+/// The other pairs cross them, as if a call was moved, and are left out.
+fn increasing_pairs(pairs: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // longest[k] is the length of the longest subsequence ending with pairs[k], and previous[k] the pair before it
+    let mut longest = vec![1; pairs.len()];
+    let mut previous = vec![None; pairs.len()];
+    for k in 0..pairs.len() {
+        for l in 0..k {
+            if pairs[l].1 < pairs[k].1 && longest[l] + 1 > longest[k] {
+                longest[k] = longest[l] + 1;
+                previous[k] = Some(l);
+            }
+        }
+    }
+
+    let mut last = (0..pairs.len()).max_by_key(|&k| longest[k]);
+    let mut subsequence = vec![];
+    while let Some(k) = last {
+        subsequence.push(pairs[k]);
+        last = previous[k];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+fn is_tweak_macro(name: &str, macros: &[String]) -> bool {
+    matches!(name, "tweak" | "release_tweak") || macros.iter().any(|alias| alias == name)
+}
+
+/// Returns the name of the macro called at the start of `src`, like `param` in `engine::param!(1.0)`.
+pub(crate) fn macro_name_at(src: &str) -> Option<&str> {
+    let tokens = tokenize(src);
+    let kinds: Vec<TokenKind> = tokens.iter().map(|token| token.kind).collect();
+
+    let mut i = 0;
+    while let [TokenKind::Ident, TokenKind::Punct(':'), TokenKind::Punct(':'), ..] = kinds[i..] {
+        i += 3;
+    }
+    match kinds.get(i..i + 3)? {
+        [TokenKind::Ident, TokenKind::Punct('!'), TokenKind::Punct('(' | '[' | '{')] => {
+            Some(&src[tokens[i].start..tokens[i].end])
+        }
+        _ => None,
+    }
+}
+
+/// Splits `src` into tokens, skipping whitespace and comments.
+/// Unterminated literals and comments extend to the end of the source.
+pub(crate) fn tokenize(src: &str) -> Vec<Token> {
+    let mut cursor = Cursor { src, pos: 0 };
+    let mut tokens = vec![];
+
+    while let Some(c) = cursor.peek(0) {
+        let start = cursor.pos;
+        let kind = match c {
+            _ if c.is_whitespace() => {
+                cursor.bump();
+                continue;
+            }
+            '/' if cursor.peek(1) == Some('/') => {
+                cursor.eat_while(|c| c != '\n');
+                continue;
+            }
+            '/' if cursor.peek(1) == Some('*') => {
+                cursor.block_comment();
+                continue;
+            }
+            '"' => {
+                cursor.bump();
+                cursor.quoted('"');
+                TokenKind::Literal
+            }
+            '\'' => cursor.char_or_lifetime(),
+            _ if cursor.prefixed_literal() => TokenKind::Literal,
+            _ if c.is_ascii_digit() => {
+                cursor.number();
+                TokenKind::Literal
+            }
+            _ if is_ident_start(c) => {
+                if cursor.rest().starts_with("r#") {
+                    cursor.pos += 2;
+                }
+                cursor.eat_while(is_ident_continue);
+                TokenKind::Ident
+            }
+            _ => {
+                cursor.bump();
+                TokenKind::Punct(c)
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            start,
+            end: cursor.pos,
+        });
+    }
+
+    tokens
+}
+
+/// Returns `src` with each run of comments and whitespace between two tokens replaced by a space,
+/// so that a tweaked value written over several commented lines can be parsed.
+pub(crate) fn strip_comments(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let push_gap = |out: &mut String, gap: &str| {
+        // Only whitespace and comments are found between tokens
+        if gap.contains('/') {
+            out.push(' ');
+        } else {
+            out.push_str(gap);
+        }
+    };
+
+    let mut end = 0;
+    for token in tokenize(src) {
+        push_gap(&mut out, &src[end..token.start]);
+        out.push_str(&src[token.start..token.end]);
+        end = token.end;
+    }
+    push_gap(&mut out, &src[end..]);
+
+    out
+}
+
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+struct Cursor<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat_while(&mut self, f: impl Fn(char) -> bool) {
+        while self.peek(0).is_some_and(&f) {
+            self.bump();
+        }
+    }
+
+    /// Skips a block comment, which may contain nested block comments.
+    fn block_comment(&mut self) {
+        let mut depth = 0u32;
+        while !self.rest().is_empty() {
+            if self.rest().starts_with("/*") {
+                self.pos += 2;
+                depth += 1;
+            } else if self.rest().starts_with("*/") {
+                self.pos += 2;
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            } else {
+                self.bump();
+            }
+        }
+    }
+
+    /// Skips the rest of a quoted literal, after its opening quote.
+    fn quoted(&mut self, quote: char) {
+        while let Some(c) = self.bump() {
+            if c == '\\' {
+                self.bump();
+            } else if c == quote {
+                return;
+            }
+        }
+    }
+
+    /// Skips a raw string, starting at its `#`s or opening quote.
+    fn raw_string(&mut self) {
+        let hashes = self.rest().chars().take_while(|&c| c == '#').count();
+        self.pos += hashes + 1;
+
+        let closing = format!("\"{}", "#".repeat(hashes));
+        match self.rest().find(&closing) {
+            Some(end) => self.pos += end + closing.len(),
+            None => self.pos = self.src.len(),
+        }
+    }
+
+    /// Skips a char literal like `'a'` or `'\n'`, or a lifetime like `'a`.
+    fn char_or_lifetime(&mut self) -> TokenKind {
+        self.bump();
+        if self.peek(0) == Some('\\') || self.peek(1) == Some('\'') {
+            self.quoted('\'');
+            return TokenKind::Literal;
+        }
+
+        self.eat_while(is_ident_continue);
+        TokenKind::Lifetime
+    }
+
+    /// Skips a literal with a prefix, like `b'a'`, `b"abc"`, `c"abc"` or `br#"abc"#`.
+    /// Returns false if there is no such literal here.
+    fn prefixed_literal(&mut self) -> bool {
+        let rest = self.rest();
+        for prefix in ["br", "cr", "r"] {
+            if let Some(after) = rest.strip_prefix(prefix) {
+                if after.trim_start_matches('#').starts_with('"') {
+                    self.pos += prefix.len();
+                    self.raw_string();
+                    return true;
+                }
+            }
+        }
+
+        for (prefix, quote) in [("b\"", '"'), ("c\"", '"'), ("b'", '\'')] {
+            if rest.starts_with(prefix) {
+                self.pos += prefix.len();
+                self.quoted(quote);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Skips a number literal, including its fraction, exponent and suffix.
+    fn number(&mut self) {
+        let start = self.pos;
+        while let Some(c) = self.peek(0) {
+            let number = &self.src[start..self.pos];
+            let is_part = is_ident_continue(c)
+                || (c == '.' && self.peek(1).is_some_and(|c| c.is_ascii_digit()))
+                || ((c == '+' || c == '-')
+                    && number.ends_with(['e', 'E'])
+                    && !number.starts_with("0x"));
+            if !is_part {
+                return;
+            }
+            self.bump();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<'a>(src: &'a str, macros: &[String]) -> Vec<&'a str> {
+        find_tweak_calls(src, macros)
+            .into_iter()
+            .map(|call| &src[call.value])
+            .collect()
+    }
+
+    /// Returns the value each call of `original` has in `current`, or `None` if it was lost.
+    fn reload<'a>(original: &str, current: &'a str) -> Vec<Option<&'a str>> {
+        let contexts = |src| -> Vec<CallContext> {
+            find_tweak_calls(src, &[])
+                .into_iter()
+                .map(|call| call.context)
+                .collect()
+        };
+        let calls = find_tweak_calls(current, &[]);
+        match_calls(&contexts(original), &contexts(current))
+            .into_iter()
+            .map(|matched| Some(&current[calls[matched?].value.clone()]))
+            .collect()
+    }
+
+    #[test]
+    fn skips_comments_and_literals() {
+        let src = r##"
+            // tweak!(0)
+            /// tweak!(0)
+            /* /* tweak!(0) */ tweak!(0) */
+            let s = "tweak!(0)";
+            let r = r#"tweak!(0)"#;
+            let c = '"'; let b = b'"';
+            let a = tweak!(1.0);
+            let b = inline_tweak::tweak!(2);
+        "##;
+        assert_eq!(values(src, &[]), ["1.0", "2"]);
+
+        let calls = find_tweak_calls(src, &[]);
+        assert!(src[calls[1].path_start..].starts_with("inline_tweak::tweak!"));
+    }
+
+    #[test]
+    fn captures_literals_with_delimiters() {
+        let src = r##"
+            let a = tweak!("smile :)");
+            let b = tweak!(';');
+            let c = tweak!("a;b");
+            let d = tweak!(vec![(1, [2]), (3, [4])]);
+            let e = tweak!(5; default());
+            let f = tweak!(r#"multi
+                line ) raw"#);
+        "##;
+        assert_eq!(
+            values(src, &[]),
+            [
+                r#""smile :)""#,
+                "';'",
+                r#""a;b""#,
+                "vec![(1, [2]), (3, [4])]",
+                "5",
+                "r#\"multi\n                line ) raw\"#"
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_bracket_and_brace_calls() {
+        let src = "
+            let a = inline_tweak::tweak![[1, 2]];
+            let b = release_tweak! {';'};
+            let c = tweak!((3));
+        ";
+        assert_eq!(values(src, &[]), ["[1, 2]", "';'", "(3)"]);
+        // Only the matching delimiter closes the call
+        assert_eq!(
+            values("tweak![f(1)] + tweak! {{1}} + 2", &[]),
+            ["f(1)", "{1}"]
+        );
+    }
+
+    #[test]
+    fn finds_forwarding_macro_calls() {
+        let src = "let a = tweak!(1.0); let f = param!(7);";
+        assert_eq!(values(src, &[]), ["1.0"]);
+        assert_eq!(values(src, &["param".to_string()]), ["1.0", "7"]);
+    }
+
+    #[test]
+    fn finds_unclosed_calls() {
+        assert_eq!(values("tweak!(1.0", &[]), ["1.0"]);
+        assert_eq!(values("tweak!(r\"a)\n", &[]), ["r\"a)\n"]);
+    }
+
+    #[test]
+    fn matches_edited_values() {
+        let original = "let a = tweak!(1); let b = tweak!(2); let c = tweak!(3);";
+        let current = "let a = tweak!(4); let b = tweak!(5); let c = tweak!(6);";
+        assert_eq!(reload(original, current), [Some("4"), Some("5"), Some("6")]);
+
+        let original = "println!(\"{}\", tweak!(1.0)); println!(\"{}\", tweak!(1.0));";
+        let current = "println!(\"{}\", tweak!(2.0)); println!(\"{}\", tweak!(3.0));";
+        assert_eq!(reload(original, current), [Some("2.0"), Some("3.0")]);
+    }
+
+    #[test]
+    fn matches_after_insert() {
+        let original = "loop {\n let width = tweak!(100);\n let height = tweak!(50);\n}";
+        let current = "loop {\n let depth = tweak!(25);\n let width = tweak!(100);\n let height = tweak!(50);\n}";
+        assert_eq!(reload(original, current), [Some("100"), Some("50")]);
+
+        let current = "loop {\n let width = tweak!(100);\n let depth = tweak!(25);\n let height = tweak!(50);\n}";
+        assert_eq!(reload(original, current), [Some("100"), Some("50")]);
+    }
+
+    #[test]
+    fn matches_after_delete() {
+        let original = "loop { let a = tweak!(1); let b = tweak!(2); let c = tweak!(3); }";
+        let current = "loop { let a = tweak!(1); let c = tweak!(3); }";
+        assert_eq!(reload(original, current), [Some("1"), None, Some("3")]);
+
+        let current = "loop { let b = tweak!(2); let c = tweak!(3); }";
+        assert_eq!(reload(original, current), [None, Some("2"), Some("3")]);
+    }
+
+    #[test]
+    fn loses_ambiguous_calls() {
+        // A duplicated line cannot be told apart from the original one
+        let original =
+            "loop {\n println!(\"{}\", tweak!(1.0));\n println!(\"{}\", tweak!(1.0));\n}";
+        let current = "loop {\n println!(\"{}\", tweak!(9.0));\n println!(\"{}\", tweak!(1.0));\n println!(\"{}\", tweak!(1.0));\n}";
+        assert_eq!(reload(original, current), [None, Some("1.0")]);
+    }
+
+    #[test]
+    fn loses_calls_edited_on_both_sides() {
+        // `a` has new code on both sides, and `b` was removed while its neighbours look alike
+        let original = "loop { let a = tweak!(1); let b = tweak!(2); let c = tweak!(3); }";
+        let current =
+            "loop { let z = tweak!(9); let y = tweak!(8); let a = tweak!(1); let c = tweak!(3); }";
+        assert_eq!(reload(original, current), [None, None, Some("3")]);
+    }
+
+    #[test]
+    fn matches_as_many_calls_in_order() {
+        let original = "fn f() { let width = tweak!(100); }";
+        let current = "fn f() { let w = tweak!(200); }";
+        assert_eq!(reload(original, current), [Some("200")]);
+
+        assert_eq!(reload("tweak!(1.0)", "tweak!(2.0) * 2.0"), [Some("2.0")]);
+
+        // Between calls found again
+        let original =
+            "let a = tweak!(1); let b = tweak!(2); let c = tweak!(3); let d = tweak!(4);";
+        let current = "let a = tweak!(1); x(tweak!(5)); y(tweak!(6)); let d = tweak!(4);";
+        assert_eq!(
+            reload(original, current),
+            [Some("1"), Some("5"), Some("6"), Some("4")]
+        );
+    }
+
+    #[test]
+    fn matches_nothing_when_all_removed() {
+        let original = "let a = tweak!(1); let b = tweak!(2);";
+        assert_eq!(reload(original, "let a = 1;"), [None, None]);
+        assert_eq!(reload("", original), []);
+    }
+
+    #[test]
+    fn strips_comments() {
+        let src = "vec![\n    1.0, // walk\n    2.5, /* run */\n]";
+        assert_eq!(strip_comments(src), "vec![\n    1.0, 2.5, ]");
+        assert_eq!(strip_comments("(1.0 /* x */, 2.0)"), "(1.0 , 2.0)");
+        assert_eq!(
+            strip_comments(r#""// not a comment" /**/"#),
+            r#""// not a comment" "#
+        );
+    }
+}
@@ -8,21 +8,26 @@
 //!
 //! ## Usage
 //!
-//! ```rust
+//! ```rust,no_run
 //! loop {
 //!     // Try changing the value while the application is running
 //!     println!("{}", inline_tweak::tweak!(3.14));
 //! }
 //! ```
 //!
+//! Tweaks can also be added or removed while the application is running, the others keep their own values.
+//! They are found again from the code around them, and the tweaks between two found ones are kept in order if none were added
+//! or removed there. A tweak which cannot be found anymore, because the code changed on both sides of it or it cannot be told apart
+//! from another one like on a duplicated line, keeps its last value with a warning.
+//!
 //! ## Extra features
 //!
 //! ### derive
 //!
-//! The `derive` feature allows to tweak any number/bool/char literal in a function.
+//! The `derive` feature allows to tweak any number/bool/char/string/byte literal in a function.
 //! It avoids cluttering the code with `inline_tweak::tweak!` calls.
 //!
-//! ```rust
+//! ```rust,ignore
 //! #[inline_tweak::tweak_fn]
 //! fn main() {
 //!     loop {
@@ -33,10 +38,30 @@
 //! }
 //! ```
 //!
+//! The `derive` feature also provides `#[derive(Tweakable)]` to tweak your own structs and enums from their literal syntax:
+//!
+//! ```rust,ignore
+//! #[derive(Clone, inline_tweak::Tweakable)]
+//! struct Vec2 {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! #[derive(Clone, inline_tweak::Tweakable)]
+//! enum Easing {
+//!     Linear,
+//!     Cubic(f32),
+//! }
+//!
+//! let pos = inline_tweak::tweak!(Vec2 { x: 1.0, y: 2.0 });
+//! let easing = inline_tweak::tweak!(Easing::Cubic(0.3)); // can be changed to Easing::Linear
+//! ```
+//!
 //! #### watch!
 //!
 //! `inline_tweak` provides a `watch!()` macro that sleeps until the file is modified, akin to a breakpoint:
-//! ```rust
+//! ```rust,no_run
+//! # use inline_tweak::watch;
 //! loop {
 //!     println!("{}", inline_tweak::tweak!(3.14));
 //!     watch!(); // The thread will sleep here until anything in the file changes
@@ -47,128 +72,270 @@
 //!
 //! `inline_tweak` allows to tweak expressions by providing a value later.
 //! For example:
-//! ```rust
+//! ```rust,ignore
 //! tweak!(rng.gen_range(0.0, 1.0))
 //! ```
 //!
 //! can then be replaced by a constant value by modifying the file (even while the application is running) to
-//! ```rust
+//! ```rust,ignore
 //! tweak!(5.0; rng.gen_range(0.0, 1.0)) // will always return 5.0
 //! ```
 //!
+//! #### Tuples and collections
+//!
+//! Tuples and fixed-size arrays of tweakable values are tweakable too, and can be nested:
+//! ```rust,no_run
+//! loop {
+//!     let color: [f32; 4] = inline_tweak::tweak!([0.2, 0.4, 0.6, 1.0]);
+//!     let (offset, scales) = inline_tweak::tweak!(((1.0, -2.0), [0.5; 3]));
+//!     println!("{:?} {:?} {:?}", color, offset, scales);
+//! }
+//! ```
+//!
+//! `Vec`, `HashMap` and `BTreeMap` are supported with the `vec![..]` and `HashMap::from([..])` syntax:
+//! ```rust,no_run
+//! # use std::collections::HashMap;
+//! loop {
+//!     let steps: Vec<u32> = inline_tweak::tweak!(vec![1, 2, 4, 8]);
+//!     let speeds: HashMap<&str, f32> = inline_tweak::tweak!(HashMap::from([("walk", 1.0), ("run", 2.5)]));
+//!     println!("{:?} {:?}", steps, speeds);
+//! }
+//! ```
+//!
+//! `Option`s are tweakable as well, so that a feature can be turned off by changing `Some(..)` to `None`:
+//! ```rust,no_run
+//! loop {
+//!     if let Some(speed) = inline_tweak::tweak!(Some(3.0)) {
+//!         println!("speed boost: {}", speed);
+//!     }
+//! }
+//! ```
+//!
+//! Ranges like `0.0..1.0`, `3..=7`, `3..` and `..7` are tweakable, as long as they keep the same kind:
+//! ```rust,no_run
+//! # use std::ops::Range;
+//! loop {
+//!     let spawn_delay: Range<f32> = inline_tweak::tweak!(0.0..1.0);
+//!     println!("{:?}", spawn_delay);
+//! }
+//! ```
+//!
+//! `Duration`s can be tweaked from their constructors, like `Duration::from_millis(200)` or `Duration::from_secs_f32(0.5)`:
+//! ```rust,no_run
+//! # use std::time::Duration;
+//! loop {
+//!     std::thread::sleep(inline_tweak::tweak!(Duration::from_millis(200)));
+//! }
+//! ```
+//!
+//! Any type implementing `FromStr` (like `IpAddr` or `PathBuf`) can be tweaked through the `TweakFromStr` wrapper:
+//! ```rust,no_run
+//! # use inline_tweak::TweakFromStr;
+//! # use std::net::IpAddr;
+//! loop {
+//!     let addr: IpAddr = inline_tweak::tweak!(TweakFromStr::new("127.0.0.1").unwrap()).0;
+//!     println!("{}", addr);
+//! }
+//! ```
+//!
+//! Flags types, like the ones generated by the `bitflags` crate, can be made tweakable by listing their constants
+//! with [`tweakable_flags!`]. They are then tweaked as `|`-combined paths:
+//! ```rust,ignore
+//! inline_tweak::tweakable_flags!(DebugDraw { WIREFRAME, NORMALS, BOUNDS, empty(), all() });
+//!
+//! loop {
+//!     let flags = inline_tweak::tweak!(DebugDraw::WIREFRAME | DebugDraw::NORMALS);
+//!     println!("{:?}", flags);
+//! }
+//! ```
+//!
+//! Colors can be tweaked through `TweakColor`, from their hex code or the CSS-like `rgb`, `rgba`, `hsl` and `hsla` constructors.
+//! It converts to `[u8; 4]` and `[f32; 4]`:
+//! ```rust,no_run
+//! # use inline_tweak::TweakColor;
+//! loop {
+//!     let color: [u8; 4] = inline_tweak::tweak!(TweakColor::hex("#ff8800")).into();
+//!     let color2: [f32; 4] = inline_tweak::tweak!(TweakColor::rgba(255, 128, 0, 0.5)).into();
+//!     println!("{:?} {:?}", color, color2);
+//! }
+//! ```
+//!
+//! #### serde
+//!
+//! Behind the `"serde"` feature flag, any type implementing `Deserialize` can be tweaked through the `TweakSerde` wrapper,
+//! using the same Rust syntax as the code:
+//! ```rust,ignore
+//! use inline_tweak::TweakSerde;
+//!
+//! #[derive(Clone, serde::Deserialize)]
+//! struct Config {
+//!     speed: f32,
+//!     names: Vec<String>,
+//! }
+//!
+//! loop {
+//!     let config = inline_tweak::tweak!(TweakSerde(Config { speed: 2.0, names: vec!["walk".to_string()] })).0;
+//!     println!("{} {:?}", config.speed, config.names);
+//! }
+//! ```
+//!
+//! #### glam and mint
+//!
+//! Behind the `"glam"` and `"mint"` feature flags, the vectors, quaternions and matrices of these libraries (glam 0.30 and mint 0.5) can be tweaked too.
+//! glam types are written with their constructors or constants, like `vec3(1.0, 2.0, 3.0)`, `Vec3::new(..)`, `Vec3::splat(0.5)`,
+//! `Vec3::ZERO` or `Quat::from_rotation_y(0.5)`. mint types are written as struct literals or converted from arrays,
+//! like `Vector3::from([1.0, 2.0, 3.0])`.
+//! ```rust,ignore
+//! use glam::{vec3, Vec3};
+//!
+//! loop {
+//!     let position: Vec3 = inline_tweak::tweak!(vec3(1.0, 2.0, 3.0));
+//!     println!("{}", position);
+//! }
+//! ```
+//!
+//! #### Wrappers and renamed macros
+//!
+//! Macros forwarding to `tweak!`, like a `param!` wrapper or `use inline_tweak::tweak as tw;`, are hot-reloaded too.
+//! Their names are learned when one of their calls is evaluated, and can be registered up front with [`register_tweak_macro`].
+//! Renamed imports of `#[tweak_fn]` in the same file are found by themselves, other attributes forwarding to it
+//! can be registered with [`register_tweak_fn_attribute`].
+//! ```rust,no_run
+//! macro_rules! param {
+//!     ($value:expr) => {
+//!         inline_tweak::tweak!($value)
+//!     };
+//! }
+//!
+//! loop {
+//!     println!("{}", param!(1.5));
+//! }
+//! ```
+//!
+//! #### Renamed dependency
+//!
+//! The macros work when `inline_tweak` is renamed in Cargo.toml or re-exported by another crate.
+//! The path to it can then be given to the derive macros with `#[tweak_fn(crate = path)]` and `#[inline_tweak(crate = path)]`:
+//! ```rust,ignore
+//! #[derive(Clone, engine::inline_tweak::Tweakable)]
+//! #[inline_tweak(crate = engine::inline_tweak)]
+//! struct Vec2 {
+//!     x: f32,
+//!     y: f32,
+//! }
+//! ```
+//!
+//! #### Source files
+//!
+//! The files are found from the directory of the crate at compile time (`CARGO_MANIFEST_DIR`) and its parents,
+//! so tweaks work whatever the current directory is, like in a workspace or under a test runner.
+//! If the sources live elsewhere at runtime, set the `INLINE_TWEAK_ROOT` environment variable to the workspace root,
+//! or call [`set_source_root`]. Paths changed by `--remap-path-prefix=FROM=TO` can be undone with
+//! [`remap_path_prefix`]`(FROM, TO)`.
+//!
 //! #### release_tweak!
 //!
 //! The `release_tweak!` macro acts exactly like `tweak!` except that it also works in release mode.
 //! It is accessible behind the feature flag `"release_tweak"` which is not enabled by default.
 #![allow(clippy::needless_doctest_main)]
 
-#[cfg(all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32")))]
+// The tokenizer is also used to parse the tweakable values, which are parsed in release mode too
+#[cfg_attr(not(all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32"))), allow(dead_code))]
 mod hasher;
 
-pub trait Tweakable: Sized + Send + Clone + 'static {
-    fn parse(x: &str) -> Option<Self>;
-}
+#[cfg_attr(not(all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32"))), allow(dead_code))]
+mod lexer;
 
-#[cfg(all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32")))]
-mod itweak {
-    use super::Tweakable;
-    use core::str::FromStr;
-    use crate::hasher::FxHashMap;
-    use std::any::Any;
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-    use std::sync::{LazyLock, Mutex};
-    use std::time::{Instant, SystemTime};
+mod aliases;
 
-    macro_rules! impl_tweakable_float {
-        ($($t: ty) +) => {
-            $(
-            impl Tweakable for $t {
-                fn parse(x: &str) -> Option<$t> {
-                    let v = x.replace("_", "").replace(stringify!($t), "");
-                    FromStr::from_str(&v).ok()
-                }
-            }
-            )+
-        };
-    }
+pub use aliases::{register_tweak_fn_attribute, register_tweak_macro};
 
-    // Follows reference https://doc.rust-lang.org/reference/expressions/literal-expr.html
-    macro_rules! impl_tweakable_integer {
-        ($($t: ty) +) => {
-            $(
-            impl Tweakable for $t {
-                fn parse(x: &str) -> Option<$t> {
-                    let s = x.replace("_", "").replace(stringify!($t), "");
-                    let radix = if s.starts_with("0x") {
-                        16
-                    } else if s.starts_with("0o") {
-                        8
-                    } else if s.starts_with("0b") {
-                        2
-                    } else {
-                        10
-                    };
-
-                    let s_without_radix = if radix == 10 {
-                        &s
-                    } else {
-                        &s[2..]
-                    };
-
-                    let v = i128::from_str_radix(&s_without_radix, radix).ok()?;
-
-                    Some(v as $t)
-                }
-            }
-            )+
-        };
-    }
+mod source_path;
 
-    impl_tweakable_integer!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 usize isize);
-    impl_tweakable_float!(f32 f64);
+pub use source_path::{remap_path_prefix, set_source_root};
 
-    impl Tweakable for bool {
-        fn parse(x: &str) -> Option<Self> {
-            match x {
-                "true" => Some(true),
-                "false" => Some(false),
-                _ => None,
-            }
-        }
-    }
+mod tweakable;
 
-    impl Tweakable for char {
-        fn parse(x: &str) -> Option<Self> {
-            x.trim_start_matches('\'')
-                .trim_end_matches('\'')
-                .chars()
-                .next()
-        }
-    }
+pub use tweakable::TweakFromStr;
 
-    impl Tweakable for &'static str {
-        fn parse(x: &str) -> Option<Self> {
-            let raw_remove = x.trim_start_matches(['r', '#']).trim_end_matches('#');
-            let remove_starting_quote = raw_remove
-                .split_once('"')
-                .map(|v| v.1)
-                .unwrap_or(raw_remove);
+mod tweak_color;
 
-            let remove_ending_quote = remove_starting_quote
-                .rsplit_once('"')
-                .map(|v| v.0)
-                .unwrap_or(remove_starting_quote);
+pub use tweak_color::TweakColor;
 
-            Some(Box::leak(Box::new(String::from(remove_ending_quote))))
-        }
+#[cfg(feature = "serde")]
+mod tweak_serde;
+
+#[cfg(feature = "serde")]
+pub use tweak_serde::TweakSerde;
+
+#[cfg(feature = "glam")]
+mod tweak_glam;
+
+#[cfg(feature = "mint")]
+mod tweak_mint;
+
+#[doc(hidden)]
+pub use tweakable::derive_helpers;
+
+pub trait Tweakable: Sized + Send + Clone + 'static {
+    fn parse(x: &str) -> Option<Self>;
+
+    /// Parses `x` like [`Tweakable::parse`], but reports the literals that cannot be represented by this type.
+    /// `Ok(None)` means that `x` is not a literal of this type (e.g. an expression), so the default value is used.
+    /// On `Err`, the error is printed and the tweak keeps its previous value.
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        Ok(Self::parse(x))
     }
+}
 
-    impl Tweakable for () {
-        fn parse(_x: &str) -> Option<Self> {
-            Some(())
+/// The error returned by [`Tweakable::try_parse`] when a literal is not valid for the type of the tweak.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TweakError {
+    /// The value does not fit in the type, like `300` for a `u8`.
+    OutOfRange { literal: String, ty: &'static str },
+    /// The suffix of the literal is another type, like `5u16` for a `u8`.
+    WrongSuffix { literal: String, ty: &'static str },
+    /// The literal is rejected by the type for another reason, like a `FromStr` error.
+    Invalid {
+        literal: String,
+        ty: &'static str,
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for TweakError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TweakError::OutOfRange { literal, ty } => {
+                write!(f, "`{}` is out of range for `{}`", literal, ty)
+            }
+            TweakError::WrongSuffix { literal, ty } => {
+                write!(f, "`{}` has a suffix that does not match `{}`", literal, ty)
+            }
+            TweakError::Invalid {
+                literal,
+                ty,
+                reason,
+            } => write!(f, "`{}` is not a valid `{}`: {}", literal, ty, reason),
         }
     }
+}
+
+impl std::error::Error for TweakError {}
+
+#[cfg(all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32")))]
+mod itweak {
+    use super::Tweakable;
+    use crate::aliases::{is_learnable_macro, tweak_macro_count, tweak_macros};
+    use crate::hasher::FxHashMap;
+    use crate::lexer::{find_tweak_calls, macro_name_at, match_calls, strip_comments, CallContext};
+    use crate::source_path::CachedSourcePath;
+    use std::any::Any;
+    use std::fs::File;
+    use std::path::Path;
+    use std::sync::{LazyLock, Mutex};
+    use std::time::{Instant, SystemTime};
 
     /// The struct holding the value of a call to tweak!.
     struct TweakValue {
@@ -185,19 +352,47 @@ mod itweak {
         /// The last time the file was checked for modifications. Avoids too many syscalls.
         last_checked_modified_time: Instant,
         file_modified: SystemTime,
-        /// The list of the literal strings.
-        values: Vec<String>,
+        /// The literal strings, by position. `None` if the call could not be found anymore.
+        values: Vec<Option<String>>,
         version: u64,
         /// The map of (line, column) -> position.
         /// This is only done once per file.
         /// This allows the line/columns to change without breaking the tweak.
         positions: Option<FxHashMap<(u32, u32), u32>>,
+        /// The contexts of the calls by position, used to find them again when the file changes.
+        contexts: Vec<CallContext>,
+        /// The content of the file when it was first parsed, to index the calls of the macros learned later.
+        source: String,
+        /// The macros forwarding to tweak! the positions were indexed with,
+        /// the registered ones and the ones learned from the calls of this file.
+        macros: Vec<String>,
+        /// The number of registered macros when the positions were indexed.
+        registered_macros: usize,
+        source_path: CachedSourcePath,
+    }
+
+    impl ParsedFile {
+        fn new() -> Self {
+            ParsedFile {
+                last_checked_modified_time: Instant::now(),
+                file_modified: SystemTime::now(),
+                values: Default::default(),
+                version: 0,
+                positions: Default::default(),
+                contexts: Default::default(),
+                source: Default::default(),
+                macros: Default::default(),
+                registered_macros: 0,
+                source_path: Default::default(),
+            }
+        }
     }
 
     #[allow(dead_code)]
     struct FileWatcher {
         last_checked: Instant,
         file_modified: SystemTime,
+        source_path: CachedSourcePath,
     }
 
     #[derive(Hash, PartialEq, Eq)]
@@ -219,40 +414,99 @@ mod itweak {
     static WATCHERS: LazyLock<Mutex<FxHashMap<Filename, FileWatcher>>> =
         LazyLock::new(Default::default);
 
-    fn last_modified(file: Filename) -> Option<SystemTime> {
+    fn last_modified(file: &Path) -> Option<SystemTime> {
         File::open(file).ok()?.metadata().ok()?.modified().ok()
     }
 
+    /// Reads a source file the way rustc does, without its byte order mark and with `\r\n` line endings
+    /// turned into `\n`, so that the lines, columns and multi-line literals match the compiled ones.
+    fn read_source(path: &Path) -> Option<String> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+        Some(content.replace("\r\n", "\n"))
+    }
+
     // Assume that the first time a tweak! is called, all tweak!s will be in original line/column.
-    fn parse_tweak_positions(file: &mut ParsedFile, filename: Filename) -> Option<()> {
-        let mut tweaks_seen = 0u32;
+    fn parse_tweak_positions(file: &mut ParsedFile, path: &Path) -> Option<()> {
+        file.source = read_source(path)?;
+        index_tweak_positions(file);
 
-        let mut positions = FxHashMap::default();
-        for (line_n, line) in BufReader::new(File::open(filename).ok()?)
-            .lines()
-            .map_while(Result::ok)
-            .enumerate()
-        {
-            for (column, _) in line.match_indices("tweak!(") {
-                let path_corrected_column = line[..column]
-                    .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == ':' || c == '_')) // https://doc.rust-lang.org/reference/paths.html follows the rust path grammar
-                    .map(|x| x + 1)
-                    .unwrap_or(0);
-
-                positions.insert(
-                    (line_n as u32 + 1, path_corrected_column as u32 + 1),
-                    tweaks_seen,
-                );
-                tweaks_seen += 1;
+        Some(())
+    }
+
+    fn index_tweak_positions(file: &mut ParsedFile) {
+        let registered = tweak_macros();
+        file.registered_macros = registered.len();
+        for name in registered {
+            if !file.macros.contains(&name) {
+                file.macros.push(name);
             }
         }
 
+        let (positions, contexts) = call_positions(&file.source, &file.macros);
         file.positions = Some(positions);
+        file.contexts = contexts;
+    }
 
-        Some(())
+    /// Returns the map of (line, column) -> position of the calls in `content`,
+    /// and their contexts by position.
+    fn call_positions(
+        content: &str,
+        macros: &[String],
+    ) -> (FxHashMap<(u32, u32), u32>, Vec<CallContext>) {
+        let mut positions = FxHashMap::default();
+        let mut contexts = vec![];
+        let (mut line_n, mut line_start) = (1, 0);
+        for (tweaks_seen, call) in find_tweak_calls(content, macros).into_iter().enumerate() {
+            let skipped = &content[line_start..call.path_start];
+            line_n += skipped.matches('\n').count() as u32;
+            if let Some(i) = skipped.rfind('\n') {
+                line_start += i + 1;
+            }
+
+            // column!() counts chars rather than bytes, a tab counting as one
+            let column = content[line_start..call.path_start].chars().count();
+            positions.insert((line_n, column as u32 + 1), tweaks_seen as u32);
+            contexts.push(call.context);
+        }
+
+        (positions, contexts)
+    }
+
+    /// Learns the macro called at `(line, column)` if it is not a known call, as it must then be a macro
+    /// forwarding to tweak!, whose `line!()` and `column!()` are the ones of the outermost call.
+    /// It is only learned for this file. The macros of std and of this crate are never learned.
+    /// Returns whether the positions must be indexed again, as more macros are known than when they were.
+    fn learn_tweak_macro(file: &mut ParsedFile, line: u32, column: u32) -> bool {
+        if file
+            .positions
+            .as_ref()
+            .is_some_and(|positions| positions.contains_key(&(line, column)))
+        {
+            return false;
+        }
+
+        let source_line = line
+            .checked_sub(1)
+            .and_then(|line| file.source.split('\n').nth(line as usize));
+        let call = source_line.and_then(|source_line| {
+            let (start, _) = source_line
+                .char_indices()
+                .nth(column.checked_sub(1)? as usize)?;
+            Some(&source_line[start..])
+        });
+        let name = call.and_then(macro_name_at).filter(|&name| {
+            is_learnable_macro(name) && !file.macros.iter().any(|known| known == name)
+        });
+        if let Some(name) = name {
+            file.macros.push(name.to_string());
+            return true;
+        }
+
+        tweak_macro_count() > file.registered_macros
     }
 
-    fn parse_tweaks(f: &mut ParsedFile, filename: Filename) -> Option<()> {
+    fn parse_tweaks(f: &mut ParsedFile, filename: Filename, path: &Path) -> Option<()> {
         if f.last_checked_modified_time.elapsed() < std::time::Duration::from_millis(500)
             && f.version != 0
         {
@@ -260,37 +514,44 @@ mod itweak {
         }
         f.last_checked_modified_time = Instant::now();
 
-        let last_modified = last_modified(filename).unwrap_or_else(SystemTime::now);
+        let last_modified = last_modified(path).unwrap_or_else(SystemTime::now);
 
         if last_modified == f.file_modified && f.version != 0 {
             return Some(());
         }
         f.file_modified = last_modified;
-        f.version += 1;
-
-        f.values.clear();
 
-        let content = std::fs::read_to_string(filename).ok()?;
-        let mut it = content.split("tweak!(");
+        reload_tweaks(f, filename, path)
+    }
 
-        it.next(); // skip part before first tweak!
+    fn reload_tweaks(f: &mut ParsedFile, filename: Filename, path: &Path) -> Option<()> {
+        f.version += 1;
 
-        for val_str in it {
-            // Find end of tweak
-            let mut prec = 1;
-            let (end, _) = val_str.char_indices().find(|(_, c)| {
-                match c {
-                    ';' | ')' if prec == 1 => {
-                        return true;
-                    }
-                    ')' => prec -= 1,
-                    '(' => prec += 1,
-                    _ => {}
-                }
-                false
-            })?;
+        f.values.clear();
 
-            f.values.push(val_str[..end].to_string());
+        let content = read_source(path)?;
+
+        let calls = find_tweak_calls(&content, &f.macros);
+        let current: Vec<CallContext> = calls.iter().map(|call| call.context).collect();
+        f.values = match_calls(&f.contexts, &current)
+            .into_iter()
+            .map(|matched| Some(strip_comments(&content[calls[matched?].value.clone()])))
+            .collect();
+
+        // Report the calls which were lost instead of giving them the value of another one
+        let mut lost: Vec<(u32, u32)> = f
+            .positions
+            .iter()
+            .flatten()
+            .filter(|(_, &position)| f.values[position as usize].is_none())
+            .map(|(&site, _)| site)
+            .collect();
+        lost.sort_unstable();
+        for (line, column) in lost {
+            eprintln!(
+                "inline_tweak: {}:{}:{}: this tweak could not be found after the edit, keeping the previous value",
+                filename, line, column
+            );
         }
 
         Some(())
@@ -298,6 +559,7 @@ mod itweak {
 
     fn update_tweak<T: Tweakable>(
         tweak: &mut TweakValue,
+        filename: Filename,
         line: u32,
         column: u32,
         file: &ParsedFile,
@@ -308,19 +570,33 @@ mod itweak {
 
         let position = file.positions.as_ref()?.get(&(line, column))?;
 
-        let value = &**file.values.get(*position as usize)?;
-
-        let parsed: Option<T> = Tweakable::parse(value);
+        let Some(value) = file.values.get(*position as usize)? else {
+            // The call was removed or could not be told apart from others, it was reported when parsing
+            tweak.file_version = file.version;
+            return Some(());
+        };
 
-        tweak.value = parsed.map(|inner| Box::new(inner) as Box<dyn Any + Send>);
+        match T::try_parse(value) {
+            Ok(parsed) => {
+                tweak.value = parsed.map(|inner| Box::new(inner) as Box<dyn Any + Send>);
+            }
+            Err(e) => eprintln!(
+                "inline_tweak: {}:{}:{}: {}, keeping the previous value",
+                filename, line, column, e
+            ),
+        }
         tweak.file_version = file.version;
 
         Some(())
     }
 
+    /// Returns the current value of the tweak, or `None` if it could not be parsed from the source.
+    /// Tweaks that are themselves an `Option` are returned as `Some(None)` when the source says `None`,
+    /// so that they are not confused with a parsing failure which falls back to the default value.
     pub(crate) fn get_value<T: Tweakable>(
         initial_value: Option<T>,
         filename: Filename,
+        manifest_dir: Option<&'static str>,
         line: u32,
         column: u32,
     ) -> Option<T> {
@@ -341,41 +617,46 @@ mod itweak {
         if tweak.last_checked.elapsed().as_secs_f32() > 0.5 {
             tweak.last_checked = Instant::now();
             let mut fileinfos = PARSED_FILES.lock().unwrap();
-            let f = fileinfos.entry(filename).or_insert_with(|| ParsedFile {
-                last_checked_modified_time: Instant::now(),
-                file_modified: SystemTime::now(),
-                values: Default::default(),
-                version: 0,
-                positions: Default::default(),
-            });
+            let f = fileinfos.entry(filename).or_insert_with(ParsedFile::new);
 
+            let path = f.source_path.get(filename, manifest_dir).to_path_buf();
             if f.positions.is_none() {
-                parse_tweak_positions(f, filename)?;
+                parse_tweak_positions(f, &path)?;
+            }
+            if learn_tweak_macro(f, line, column) {
+                index_tweak_positions(f);
+                reload_tweaks(f, filename, &path)?;
             }
 
-            parse_tweaks(f, filename)?;
+            parse_tweaks(f, filename, &path)?;
 
-            update_tweak::<T>(tweak, line, column, f)?;
+            update_tweak::<T>(tweak, filename, line, column, f)?;
         }
 
         tweak.value.as_ref()?.downcast_ref().cloned()
     }
 
     #[allow(dead_code)]
-    pub fn watch_modified(file: Filename) -> bool {
+    pub fn watch_modified(file: Filename, manifest_dir: Option<&'static str>) -> bool {
         let mut lock = WATCHERS.lock().unwrap();
         let entry = lock.entry(file);
 
         let now = Instant::now();
 
-        let watcher = entry.or_insert_with(|| FileWatcher {
-            last_checked: now,
-            file_modified: last_modified(file).unwrap_or_else(SystemTime::now),
+        let watcher = entry.or_insert_with(|| {
+            let mut source_path = CachedSourcePath::default();
+            FileWatcher {
+                last_checked: now,
+                file_modified: last_modified(source_path.get(file, manifest_dir))
+                    .unwrap_or_else(SystemTime::now),
+                source_path,
+            }
         });
 
         watcher.last_checked = now;
 
-        let last_modified = last_modified(file).unwrap_or_else(SystemTime::now);
+        let last_modified = last_modified(watcher.source_path.get(file, manifest_dir))
+            .unwrap_or_else(SystemTime::now);
         last_modified
             .duration_since(watcher.file_modified)
             .map(|time| {
@@ -385,12 +666,84 @@ mod itweak {
             .unwrap_or(true)
     }
 
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn indexes_calls_like_rustc() {
+            let path = std::env::temp_dir().join(format!("inline_tweak_{}.rs", std::process::id()));
+            let content = "\u{feff}let é = tweak!(1);\r\n\tlet b = [tweak!(2), tweak!(\"a\r\nb\")]; let c = tweak!(3);\r\n";
+            std::fs::write(&path, content).unwrap();
+            let source = read_source(&path);
+            std::fs::remove_file(&path).unwrap();
+
+            // Without the byte order mark, and with `\n` line endings, including in literals
+            let source = source.unwrap();
+            assert_eq!(
+                source,
+                "let é = tweak!(1);\n\tlet b = [tweak!(2), tweak!(\"a\nb\")]; let c = tweak!(3);\n"
+            );
+
+            // Columns count chars, a tab counting as one
+            let (positions, _) = call_positions(&source, &[]);
+            let mut positions: Vec<_> = positions.into_iter().collect();
+            positions.sort_unstable();
+            assert_eq!(
+                positions,
+                [((1, 9), 0), ((2, 11), 1), ((2, 22), 2), ((3, 15), 3)]
+            );
+        }
+
+        /// The registered macros are global, so they are only registered in this test.
+        #[test]
+        fn learns_forwarding_macros() {
+            let mut file = ParsedFile::new();
+            file.source =
+                "let a = tweak!(1.0);\nlet b = param!(2.0);\nprintln!(\"{}\", wrapper!(3.0));\n"
+                    .to_string();
+            index_tweak_positions(&mut file);
+            let position =
+                |file: &ParsedFile, site| file.positions.as_ref().unwrap().get(&site).copied();
+            assert_eq!(position(&file, (2, 9)), None);
+
+            // A known call
+            assert!(!learn_tweak_macro(&mut file, 1, 9));
+            // The call of a wrapper, learned for this file only
+            assert!(learn_tweak_macro(&mut file, 2, 9));
+            assert_eq!(file.macros, ["param"]);
+            assert!(!tweak_macros().contains(&"param".to_string()));
+            index_tweak_positions(&mut file);
+            assert_eq!(position(&file, (2, 9)), Some(1));
+            assert!(!learn_tweak_macro(&mut file, 2, 9));
+            // The macros of std are never learned
+            assert!(!learn_tweak_macro(&mut file, 3, 1));
+
+            // Registered macros are used by every file once indexed again
+            crate::register_tweak_macro("wrapper");
+            crate::register_tweak_macro("wrapper");
+            assert_eq!(
+                tweak_macros()
+                    .iter()
+                    .filter(|name| *name == "wrapper")
+                    .count(),
+                1
+            );
+            assert!(learn_tweak_macro(&mut file, 3, 1));
+            index_tweak_positions(&mut file);
+            assert_eq!(file.macros, ["param", "wrapper"]);
+            assert_eq!(position(&file, (3, 16)), Some(2));
+            assert!(!learn_tweak_macro(&mut file, 3, 1));
+        }
+    }
+
     #[cfg(feature = "derive")]
     pub(crate) mod derive {
         use super::*;
 
-        use crate::Tweakable;
+        use crate::aliases::{tweak_fn_attribute_count, tweak_fn_attributes};
         use crate::hasher::FxHashMap;
+        use crate::Tweakable;
         use std::any::Any;
         use std::hash::{Hash, Hasher};
         use std::sync::Mutex;
@@ -398,7 +751,8 @@ mod itweak {
         use syn::spanned::Spanned;
         use syn::visit::Visit;
         use syn::{
-            Attribute, ExprConst, ImplItemFn, ItemConst, ItemFn, ItemStatic, Lit, TraitItemFn, Type,
+            Attribute, ExprConst, ImplItemFn, ItemConst, ItemFn, ItemStatic, Lit, Pat, TraitItemFn,
+            Type, UseRename,
         };
 
         struct ParsedFile {
@@ -408,6 +762,9 @@ mod itweak {
             /// Map of function name to the literal strings
             values: FxHashMap<String, Vec<String>>,
             version: u64,
+            /// The number of registered tweak_fn attributes when the file was parsed.
+            registered_attributes: usize,
+            source_path: CachedSourcePath,
         }
 
         /// Stores the values of the tweaks. The key is the file, the function name and the nth tweak
@@ -427,13 +784,30 @@ mod itweak {
             fname_hash: u64, // Store a hash of the function name to avoid borrowing constraints
         }
 
-        /// Visiter that finds all number/bool/char literals in a function.
+        /// Visiter that finds all number/bool/char/string/byte literals in a function.
         struct LiteralFinder<'a> {
             file: &'a mut ParsedFile,
+            /// The names of the attributes making a function tweakable.
+            attributes: &'a [String],
             inside_derive_fn: Option<String>,
             derive_fn_count: u32,
         }
 
+        /// Visiter that finds the renamed imports of the tweak_fn attributes, like `use inline_tweak::tweak_fn as tf;`.
+        struct AttributeRenames<'a> {
+            attributes: &'a mut Vec<String>,
+        }
+
+        impl<'ast> Visit<'ast> for AttributeRenames<'_> {
+            fn visit_use_rename(&mut self, i: &'ast UseRename) {
+                if self.attributes.iter().any(|name| i.ident == name)
+                    && !self.attributes.iter().any(|name| i.rename == name)
+                {
+                    self.attributes.push(i.rename.to_string());
+                }
+            }
+        }
+
         impl<'a> LiteralFinder<'a> {
             fn enter_fn(
                 &mut self,
@@ -447,7 +821,7 @@ mod itweak {
                     attr.path()
                         .segments
                         .last()
-                        .map(|seg| seg.ident == "tweak_fn" || seg.ident == "release_tweak_fn")
+                        .map(|seg| self.attributes.iter().any(|name| seg.ident == name))
                         .unwrap_or(false)
                 }) {
                     self.inside_derive_fn = Some(fn_name);
@@ -474,7 +848,14 @@ mod itweak {
 
             fn visit_lit(&mut self, l: &'ast Lit) {
                 match l {
-                    Lit::Char(_) | Lit::Int(_) | Lit::Float(_) | Lit::Bool(_) | Lit::Str(_) => {}
+                    Lit::Char(_)
+                    | Lit::Int(_)
+                    | Lit::Float(_)
+                    | Lit::Bool(_)
+                    | Lit::Str(_)
+                    | Lit::Byte(_)
+                    | Lit::ByteStr(_)
+                    | Lit::CStr(_) => {}
                     _ => return,
                 }
 
@@ -538,10 +919,13 @@ mod itweak {
 
             fn visit_item_static(&mut self, _: &ItemStatic) {}
 
+            /// Literals in patterns are not replaced by tweak_fn, so they must not be counted.
+            fn visit_pat(&mut self, _: &Pat) {}
+
             fn visit_type(&mut self, _: &Type) {}
         }
 
-        fn parse_tweaks_derive<'a>(f: &mut ParsedFile, filename: Filename) -> Option<()> {
+        fn parse_tweaks_derive(f: &mut ParsedFile, path: &Path) -> Option<()> {
             if f.last_checked_modified_time.elapsed() < std::time::Duration::from_millis(500)
                 && f.version != 0
             {
@@ -549,20 +933,37 @@ mod itweak {
             }
 
             f.last_checked_modified_time = Instant::now();
-            let last_modified = last_modified(filename).unwrap_or_else(SystemTime::now);
+            let last_modified = last_modified(path).unwrap_or_else(SystemTime::now);
 
-            if last_modified == f.file_modified {
+            let registered = tweak_fn_attribute_count();
+            if last_modified == f.file_modified && registered == f.registered_attributes {
                 return Some(());
             }
             f.file_modified = last_modified;
+            f.registered_attributes = registered;
 
-            let content = std::fs::read_to_string(filename).ok()?;
+            let content = read_source(path)?;
             let parsed = syn::parse_file(&content).ok()?;
 
+            let mut attributes = tweak_fn_attributes();
+            attributes.extend(["tweak_fn".to_string(), "release_tweak_fn".to_string()]);
+            // Until no new rename is found, for renames of renames
+            loop {
+                let known = attributes.len();
+                AttributeRenames {
+                    attributes: &mut attributes,
+                }
+                .visit_file(&parsed);
+                if attributes.len() == known {
+                    break;
+                }
+            }
+
             f.values.clear();
             LiteralFinder {
                 inside_derive_fn: None,
                 file: f,
+                attributes: &attributes,
                 derive_fn_count: 0,
             }
             .visit_file(&parsed);
@@ -574,6 +975,7 @@ mod itweak {
 
         pub(crate) fn get_value_derive<T: Tweakable>(
             filename: Filename,
+            manifest_dir: Option<&'static str>,
             function_name: &'static str,
             nth: u32,
         ) -> Option<T> {
@@ -581,7 +983,7 @@ mod itweak {
 
             let tweak = lock
                 .entry(DeriveValueKey {
-                    filename: filename,
+                    filename,
                     nth,
                     fname_hash: {
                         let mut hasher = crate::hasher::FxHasher::default();
@@ -603,11 +1005,14 @@ mod itweak {
                     file_modified: SystemTime::now(),
                     values: Default::default(),
                     version: 0,
+                    registered_attributes: 0,
+                    source_path: Default::default(),
                 });
 
-                parse_tweaks_derive(f, filename)?;
+                let path = f.source_path.get(filename, manifest_dir).to_path_buf();
+                parse_tweaks_derive(f, &path)?;
 
-                update_tweak_derive::<T>(tweak, function_name, nth, f)?;
+                update_tweak_derive::<T>(tweak, filename, function_name, nth, f)?;
             }
 
             tweak.value.as_ref()?.downcast_ref().cloned()
@@ -615,6 +1020,7 @@ mod itweak {
 
         fn update_tweak_derive<T: Tweakable>(
             tweak: &mut TweakValue,
+            filename: Filename,
             function_name: &'static str,
             nth: u32,
             file: &ParsedFile,
@@ -625,9 +1031,15 @@ mod itweak {
 
             let value = &**file.values.get(function_name)?.get(nth as usize)?;
 
-            let parsed: Option<T> = Tweakable::parse(value);
-
-            tweak.value = parsed.map(|inner| Box::new(inner) as Box<dyn Any + Send>);
+            match T::try_parse(value) {
+                Ok(parsed) => {
+                    tweak.value = parsed.map(|inner| Box::new(inner) as Box<dyn Any + Send>);
+                }
+                Err(e) => eprintln!(
+                    "inline_tweak: {}: literal #{} of `{}`: {}, keeping the previous value",
+                    filename, nth, function_name, e
+                ),
+            }
             tweak.file_version = file.version;
 
             Some(())
@@ -642,7 +1054,20 @@ pub fn inline_tweak<T: Tweakable>(
     line: u32,
     column: u32,
 ) -> Option<T> {
-    itweak::get_value(initial_value, filename, line, column)
+    itweak::get_value(initial_value, filename, None, line, column)
+}
+
+/// Like [`inline_tweak`], also given the `CARGO_MANIFEST_DIR` of the crate of the tweak to find its source file.
+#[cfg(all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32")))]
+#[doc(hidden)]
+pub fn inline_tweak_in_crate<T: Tweakable>(
+    initial_value: Option<T>,
+    manifest_dir: Option<&'static str>,
+    filename: &'static str,
+    line: u32,
+    column: u32,
+) -> Option<T> {
+    itweak::get_value(initial_value, filename, manifest_dir, line, column)
 }
 
 #[cfg(all(feature = "derive", all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32"))))]
@@ -651,7 +1076,19 @@ pub fn inline_tweak_derive<T: Tweakable>(
     function_name: &'static str,
     nth: u32,
 ) -> Option<T> {
-    itweak::derive::get_value_derive(file, function_name, nth)
+    itweak::derive::get_value_derive(file, None, function_name, nth)
+}
+
+/// Like [`inline_tweak_derive`], also given the `CARGO_MANIFEST_DIR` of the crate of the tweak to find its source file.
+#[cfg(all(feature = "derive", all(any(debug_assertions, feature = "release_tweak"), not(target_arch = "wasm32"))))]
+#[doc(hidden)]
+pub fn inline_tweak_derive_in_crate<T: Tweakable>(
+    manifest_dir: Option<&'static str>,
+    file: &'static str,
+    function_name: &'static str,
+    nth: u32,
+) -> Option<T> {
+    itweak::derive::get_value_derive(file, manifest_dir, function_name, nth)
 }
 
 #[cfg(all(feature = "release_tweak", not(target_arch = "wasm32")))]
@@ -659,19 +1096,37 @@ mod macros_release {
     #[macro_export]
     macro_rules! release_tweak {
         ($default:expr) => {
-            inline_tweak::inline_tweak(None, std::file!(), std::line!(), std::column!())
-                .unwrap_or_else(|| $default)
+            $crate::inline_tweak_in_crate(
+                None,
+                std::option_env!("CARGO_MANIFEST_DIR"),
+                std::file!(),
+                std::line!(),
+                std::column!(),
+            )
+            .unwrap_or_else(|| $default)
         };
         ($value:literal; $default:expr) => {
-            inline_tweak::inline_tweak(Some($value), std::file!(), std::line!(), std::column!())
-                .unwrap_or_else(|| $default)
+            $crate::inline_tweak_in_crate(
+                Some($value),
+                std::option_env!("CARGO_MANIFEST_DIR"),
+                std::file!(),
+                std::line!(),
+                std::column!(),
+            )
+            .unwrap_or_else(|| $default)
         };
     }
 
     #[macro_export]
     macro_rules! derive_release_tweak {
         ($default:expr, $fn_name:expr, $position:expr) => {
-            inline_tweak::inline_tweak_derive(std::file!(), $fn_name, $position).unwrap_or($default)
+            $crate::inline_tweak_derive_in_crate(
+                std::option_env!("CARGO_MANIFEST_DIR"),
+                std::file!(),
+                $fn_name,
+                $position,
+            )
+            .unwrap_or($default)
         };
     }
 }
@@ -691,8 +1146,13 @@ mod macros_release {
     #[macro_export]
     macro_rules! derive_release_tweak {
         ($default:expr, $fn_name:expr, $position:expr) => {
-            inline_tweak::inline_tweak_derive(std::file!(), $fn_name, $position)
-                .unwrap_or_else(|| $default)
+            $crate::inline_tweak_derive_in_crate(
+                std::option_env!("CARGO_MANIFEST_DIR"),
+                std::file!(),
+                $fn_name,
+                $position,
+            )
+            .unwrap_or_else(|| $default)
         };
     }
 }
@@ -703,12 +1163,24 @@ mod macros_tweak {
     #[macro_export]
     macro_rules! tweak {
         ($default:expr) => {
-            inline_tweak::inline_tweak(None, std::file!(), std::line!(), std::column!())
-                .unwrap_or_else(|| $default)
+            $crate::inline_tweak_in_crate(
+                None,
+                std::option_env!("CARGO_MANIFEST_DIR"),
+                std::file!(),
+                std::line!(),
+                std::column!(),
+            )
+            .unwrap_or_else(|| $default)
         };
         ($value:literal; $default:expr) => {
-            inline_tweak::inline_tweak(Some($value), std::file!(), std::line!(), std::column!())
-                .unwrap_or_else(|| $default)
+            $crate::inline_tweak_in_crate(
+                Some($value),
+                std::option_env!("CARGO_MANIFEST_DIR"),
+                std::file!(),
+                std::line!(),
+                std::column!(),
+            )
+            .unwrap_or_else(|| $default)
         };
     }
 
@@ -717,7 +1189,13 @@ mod macros_tweak {
     #[macro_export]
     macro_rules! derive_tweak {
         ($default:expr, $fn_name:expr, $position:expr) => {
-            inline_tweak::inline_tweak_derive(std::file!(), $fn_name, $position).unwrap_or($default)
+            $crate::inline_tweak_derive_in_crate(
+                std::option_env!("CARGO_MANIFEST_DIR"),
+                std::file!(),
+                $fn_name,
+                $position,
+            )
+            .unwrap_or($default)
         };
     }
 
@@ -731,8 +1209,8 @@ mod macros_tweak {
     }
 
     #[doc(hidden)]
-    pub fn watch_file(filename: &'static str) {
-        while !itweak::watch_modified(filename) {
+    pub fn watch_file(filename: &'static str, manifest_dir: Option<&'static str>) {
+        while !itweak::watch_modified(filename, manifest_dir) {
             std::thread::sleep(std::time::Duration::from_millis(500));
         }
     }
@@ -740,7 +1218,7 @@ mod macros_tweak {
     #[macro_export]
     macro_rules! watch {
         () => {
-            inline_tweak::watch_file(std::file!());
+            $crate::watch_file(std::file!(), std::option_env!("CARGO_MANIFEST_DIR"));
         };
     }
 }
@@ -766,7 +1244,7 @@ mod macros_tweak {
     }
 
     #[doc(hidden)]
-    pub fn watch_file(_filename: &'static str) {}
+    pub fn watch_file(_filename: &'static str, _manifest_dir: Option<&'static str>) {}
 
     #[macro_export]
     macro_rules! watch {
@@ -774,6 +1252,52 @@ mod macros_tweak {
     }
 }
 
+/// Implements [`Tweakable`] for a flags type, like the ones generated by the `bitflags` crate, from its named constants.
+/// The value can then be tweaked as `|`-combined paths to these constants, like `Flags::A | Flags::B`.
+/// Associated functions without arguments, like `empty()` or `all()`, can be listed too.
+///
+/// The type can be given by its path, and must implement `BitOr<Output = Self>`.
+/// A constant which is not listed, like a typo in `Flags::WIERFRAME`, is reported and the tweak keeps its previous value.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// bitflags::bitflags! {
+///     #[derive(Clone, Copy)]
+///     struct Debug: u32 {
+///         const WIREFRAME = 1;
+///         const NORMALS = 1 << 1;
+///     }
+/// }
+///
+/// inline_tweak::tweakable_flags!(Debug { WIREFRAME, NORMALS, empty(), all() });
+///
+/// let debug = inline_tweak::tweak!(Debug::WIREFRAME | Debug::NORMALS);
+/// ```
+#[macro_export]
+macro_rules! tweakable_flags {
+    ($ty:path { $($flag:ident $(($($args:tt)*))?),* $(,)? }) => {
+        impl $crate::Tweakable for $ty {
+            fn parse(x: &str) -> ::core::option::Option<Self> {
+                <Self as $crate::Tweakable>::try_parse(x).ok().flatten()
+            }
+
+            fn try_parse(
+                x: &str,
+            ) -> ::core::result::Result<::core::option::Option<Self>, $crate::TweakError> {
+                $crate::derive_helpers::flags(
+                    x,
+                    ::core::stringify!($ty),
+                    &[$((
+                        ::core::concat!(::core::stringify!($flag) $(, "(", $(::core::stringify!($args),)* ")")?),
+                        <$ty>::$flag $(($($args)*))?,
+                    )),*],
+                )
+            }
+        }
+    };
+}
+
 pub use macros_tweak::*;
 
 #[cfg(feature = "derive")]
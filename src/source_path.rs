@@ -0,0 +1,175 @@
+//! Resolution of the `file!()` paths of the tweaks to the source files on disk.
+//!
+//! `file!()` is relative to the directory cargo was run from, usually the workspace root,
+//! so opening it relative to the current directory fails when the program runs from elsewhere.
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+static SOURCE_ROOT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// The `(from, to)` prefixes given to `--remap-path-prefix`.
+static REMAPPED_PREFIXES: Mutex<Vec<(PathBuf, PathBuf)>> = Mutex::new(Vec::new());
+
+/// Incremented when the source root or the remapped prefixes change, so that the cached paths are resolved again.
+static CONFIG_VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// Sets the directory the source files of the tweaks are relative to, usually the workspace root.
+/// It takes precedence over the `INLINE_TWEAK_ROOT` environment variable.
+///
+/// This is only needed when the sources cannot be found from the current directory
+/// nor from the directory of the crate at compile time, e.g when the binary was moved to another machine.
+pub fn set_source_root(root: impl Into<PathBuf>) {
+    *SOURCE_ROOT.lock().unwrap() = Some(root.into());
+    CONFIG_VERSION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Undoes a `--remap-path-prefix=FROM=TO` flag passed to rustc, so that the remapped `file!()` paths
+/// starting with `to` are read from `from` again. Takes the same arguments as the flag.
+pub fn remap_path_prefix(from: impl Into<PathBuf>, to: impl Into<PathBuf>) {
+    REMAPPED_PREFIXES
+        .lock()
+        .unwrap()
+        .push((from.into(), to.into()));
+    CONFIG_VERSION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the path to read `file` from, `file` being the `file!()` of a tweak of the crate in `manifest_dir`.
+///
+/// In order, the remapped prefixes are undone, then relative paths are looked up from the source root
+/// if one is set, or else from the manifest directory and its parents (to find the workspace root),
+/// and finally from the current directory.
+#[cfg(all(
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn source_path(file: &str, manifest_dir: Option<&str>) -> PathBuf {
+    let mut path = PathBuf::from(file);
+    // The last flag wins in rustc, so do the same
+    for (from, to) in REMAPPED_PREFIXES.lock().unwrap().iter().rev() {
+        if let Ok(rest) = path.strip_prefix(to) {
+            path = from.join(rest);
+            break;
+        }
+    }
+
+    if path.is_absolute() {
+        return path;
+    }
+
+    let root = SOURCE_ROOT.lock().unwrap().clone();
+    if let Some(root) = root.or_else(|| std::env::var_os("INLINE_TWEAK_ROOT").map(PathBuf::from)) {
+        return root.join(path);
+    }
+
+    manifest_dir
+        .into_iter()
+        .flat_map(|dir| std::path::Path::new(dir).ancestors())
+        .map(|dir| dir.join(&path))
+        .find(|candidate| candidate.is_file())
+        .unwrap_or(path)
+}
+
+/// The path of a source file, resolved once by [`source_path`] as it looks up the parent directories,
+/// and again only when [`set_source_root`] or [`remap_path_prefix`] are called.
+/// The `INLINE_TWEAK_ROOT` environment variable is read when the path is resolved.
+#[cfg(all(
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+#[derive(Default)]
+pub(crate) struct CachedSourcePath {
+    path: PathBuf,
+    /// The `CONFIG_VERSION` the path was resolved with, `None` if it was not resolved yet.
+    version: Option<u64>,
+}
+
+#[cfg(all(
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+impl CachedSourcePath {
+    /// Returns the path to read `file` from, like [`source_path`].
+    pub(crate) fn get(&mut self, file: &str, manifest_dir: Option<&str>) -> &std::path::Path {
+        let version = CONFIG_VERSION.load(Ordering::Relaxed);
+        if self.version != Some(version) {
+            self.path = source_path(file, manifest_dir);
+            self.version = Some(version);
+        }
+        &self.path
+    }
+}
+
+#[cfg(all(
+    test,
+    any(debug_assertions, feature = "release_tweak"),
+    not(target_arch = "wasm32")
+))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// The settings are global, so they are all tested here, one after the other.
+    #[test]
+    fn resolves_source_paths() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let member_dir = root.join("inline_tweak_derive");
+        let member = member_dir.to_str();
+
+        // From the crate directory, or the workspace root for a workspace member
+        assert_eq!(
+            source_path("src/lib.rs", root.to_str()),
+            root.join("src/lib.rs")
+        );
+        assert_eq!(
+            source_path("inline_tweak_derive/src/tweakable.rs", member),
+            root.join("inline_tweak_derive/src/tweakable.rs")
+        );
+        assert_eq!(
+            source_path("src/missing.rs", member),
+            Path::new("src/missing.rs")
+        );
+
+        let mut cached = CachedSourcePath::default();
+        assert_eq!(
+            cached.get("src/lib.rs", member),
+            member_dir.join("src/lib.rs")
+        );
+
+        std::env::set_var("INLINE_TWEAK_ROOT", "/env_root");
+        assert_eq!(
+            source_path("src/lib.rs", member),
+            Path::new("/env_root/src/lib.rs")
+        );
+
+        set_source_root("/set_root");
+        assert_eq!(
+            source_path("src/lib.rs", member),
+            Path::new("/set_root/src/lib.rs")
+        );
+        // The cached path is resolved again once the root is set
+        assert_eq!(
+            cached.get("src/lib.rs", member),
+            Path::new("/set_root/src/lib.rs")
+        );
+
+        remap_path_prefix("/home/me/project", "/remapped");
+        remap_path_prefix("/home/me/other", "/remapped");
+        assert_eq!(
+            source_path("/remapped/src/lib.rs", member),
+            Path::new("/home/me/other/src/lib.rs")
+        );
+        assert_eq!(
+            cached.get("/remapped/src/lib.rs", member),
+            Path::new("/home/me/other/src/lib.rs")
+        );
+        assert_eq!(
+            source_path("/elsewhere/src/lib.rs", member),
+            Path::new("/elsewhere/src/lib.rs")
+        );
+
+        *SOURCE_ROOT.lock().unwrap() = None;
+        REMAPPED_PREFIXES.lock().unwrap().clear();
+        std::env::remove_var("INLINE_TWEAK_ROOT");
+    }
+}
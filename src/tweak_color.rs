@@ -0,0 +1,280 @@
+//! A color type which can be tweaked from its hex code or from the CSS-like color functions.
+use crate::tweakable::{parse_str_literal, split_top_level, strip_call, try_some, StrKind};
+use crate::{TweakError, Tweakable};
+
+/// An RGBA color with components between 0 and 1, tweakable from its hex code like `"#ff8800"` or `"#ff8800cc"`,
+/// or from the CSS-like `rgb`, `rgba`, `hsl` and `hsla` constructors.
+/// It converts to `[u8; 4]` and `[f32; 4]` to be used with any graphics library.
+///
+/// The constructors can also be written without the `TweakColor::` prefix while the application is running,
+/// and the hex code can be written as a bare string literal.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inline_tweak::TweakColor;
+///
+/// loop {
+///     let color: [u8; 4] = inline_tweak::tweak!(TweakColor::hex("#ff8800")).into();
+///     println!("{:?}", color);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TweakColor {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl TweakColor {
+    /// Parses a hex code like `"#f80"`, `"#f80c"`, `"#ff8800"` or `"#ff8800cc"`. The `#` is optional.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hex` is not a valid hex code, as it is the value written in the source code.
+    pub fn hex(hex: &str) -> Self {
+        match Self::try_hex(hex) {
+            Some(color) => color,
+            None => panic!("TweakColor: {:?} is not a valid hex color", hex),
+        }
+    }
+
+    /// Parses a hex code like [`TweakColor::hex`], returning `None` if it is not valid.
+    pub fn try_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim();
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |i: usize, len: usize| {
+            let v = u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).unwrap();
+            if len == 1 {
+                v * 17
+            } else {
+                v
+            }
+        };
+
+        let [r, g, b, a] = match digits.len() {
+            3 => [channel(0, 1), channel(1, 1), channel(2, 1), 255],
+            4 => [channel(0, 1), channel(1, 1), channel(2, 1), channel(3, 1)],
+            6 => [channel(0, 2), channel(1, 2), channel(2, 2), 255],
+            8 => [channel(0, 2), channel(1, 2), channel(2, 2), channel(3, 2)],
+            _ => return None,
+        };
+        Some(Self::rgba(r, g, b, a as f32 / 255.0))
+    }
+
+    /// An opaque color from its red, green and blue components between 0 and 255.
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    /// A color from its red, green and blue components between 0 and 255, and its alpha between 0 and 1.
+    pub fn rgba(r: u8, g: u8, b: u8, a: f32) -> Self {
+        Self {
+            r: r as f32 / 255.0,
+            g: g as f32 / 255.0,
+            b: b as f32 / 255.0,
+            a,
+        }
+    }
+
+    /// An opaque color from its hue in degrees, and its saturation and lightness between 0 and 1.
+    pub fn hsl(h: f32, s: f32, l: f32) -> Self {
+        Self::hsla(h, s, l, 1.0)
+    }
+
+    /// A color from its hue in degrees, and its saturation, lightness and alpha between 0 and 1.
+    pub fn hsla(h: f32, s: f32, l: f32, a: f32) -> Self {
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let sector = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let m = l - chroma / 2.0;
+        Self {
+            r: r + m,
+            g: g + m,
+            b: b + m,
+            a,
+        }
+    }
+
+    /// Returns the components between 0 and 255.
+    pub fn to_rgba8(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Returns the components between 0 and 1.
+    pub fn to_array(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+}
+
+impl From<TweakColor> for [u8; 4] {
+    fn from(color: TweakColor) -> Self {
+        color.to_rgba8()
+    }
+}
+
+impl From<TweakColor> for [f32; 4] {
+    fn from(color: TweakColor) -> Self {
+        color.to_array()
+    }
+}
+
+/// Supports `TweakColor::hex("#ff8800")`, `TweakColor::rgb(255, 128, 0)`, `TweakColor::rgba(255, 128, 0, 0.5)`,
+/// `TweakColor::hsl(30.0, 1.0, 0.5)` and `TweakColor::hsla(30.0, 1.0, 0.5, 0.5)`, with or without the prefix,
+/// and a bare hex code string. Invalid hex codes and out of range components are reported.
+impl Tweakable for TweakColor {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        let x = x.trim();
+        if let Some(args) = strip_call(x, "rgb") {
+            let [r, g, b] = try_some!(parse_args(args));
+            return Ok(Some(Self::rgb(
+                try_some!(u8::try_parse(r)?),
+                try_some!(u8::try_parse(g)?),
+                try_some!(u8::try_parse(b)?),
+            )));
+        }
+        if let Some(args) = strip_call(x, "rgba") {
+            let [r, g, b, a] = try_some!(parse_args(args));
+            return Ok(Some(Self::rgba(
+                try_some!(u8::try_parse(r)?),
+                try_some!(u8::try_parse(g)?),
+                try_some!(u8::try_parse(b)?),
+                try_some!(f32::try_parse(a)?),
+            )));
+        }
+        if let Some(args) = strip_call(x, "hsl") {
+            let [h, s, l] = try_some!(parse_args(args));
+            return Ok(Some(Self::hsl(
+                try_some!(f32::try_parse(h)?),
+                try_some!(f32::try_parse(s)?),
+                try_some!(f32::try_parse(l)?),
+            )));
+        }
+        if let Some(args) = strip_call(x, "hsla") {
+            let [h, s, l, a] = try_some!(parse_args(args));
+            return Ok(Some(Self::hsla(
+                try_some!(f32::try_parse(h)?),
+                try_some!(f32::try_parse(s)?),
+                try_some!(f32::try_parse(l)?),
+                try_some!(f32::try_parse(a)?),
+            )));
+        }
+
+        let hex = try_some!(parse_str_literal(
+            strip_call(x, "hex").unwrap_or(x),
+            StrKind::Str
+        ));
+        let hex = try_some!(std::str::from_utf8(&hex).ok());
+        match Self::try_hex(hex) {
+            Some(color) => Ok(Some(color)),
+            None => Err(TweakError::Invalid {
+                literal: x.to_string(),
+                ty: "TweakColor",
+                reason: format!("{:?} is not a valid hex color", hex),
+            }),
+        }
+    }
+}
+
+/// Splits the `N` arguments of a constructor.
+fn parse_args<const N: usize>(args: &str) -> Option<[&str; N]> {
+    split_top_level(args)?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba8(x: &str) -> Option<[u8; 4]> {
+        TweakColor::parse(x).map(TweakColor::to_rgba8)
+    }
+
+    #[test]
+    fn parses_hex_codes() {
+        assert_eq!(
+            rgba8(r##"TweakColor::hex("#ff8800")"##),
+            Some([255, 136, 0, 255])
+        );
+        assert_eq!(
+            rgba8(r##"inline_tweak::TweakColor::hex("#ff8800cc")"##),
+            Some([255, 136, 0, 204])
+        );
+        assert_eq!(rgba8(r##""#f80""##), Some([255, 136, 0, 255]));
+        assert_eq!(rgba8(r##"hex("f80c")"##), Some([255, 136, 0, 204]));
+        assert_eq!(rgba8(r##""#ff88""##), Some([255, 255, 136, 136]));
+
+        for x in [r##""#ff88zz""##, r##""#ff888""##, r##"hex("")"##] {
+            assert!(matches!(
+                TweakColor::try_parse(x),
+                Err(TweakError::Invalid { .. })
+            ));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not a valid hex color")]
+    fn hex_panics_on_invalid_codes() {
+        TweakColor::hex("#ff88zz");
+    }
+
+    #[test]
+    fn parses_constructors() {
+        assert_eq!(
+            rgba8("TweakColor::rgb(255, 128, 0)"),
+            Some([255, 128, 0, 255])
+        );
+        assert_eq!(rgba8("rgb(255, 128, 0)"), Some([255, 128, 0, 255]));
+        assert_eq!(rgba8("rgba(255, 128, 0, 0.5)"), Some([255, 128, 0, 128]));
+        assert_eq!(rgba8("hsl(30.0, 1.0, 0.5)"), Some([255, 128, 0, 255]));
+        assert_eq!(rgba8("hsl(0.0, 1.0, 0.5)"), Some([255, 0, 0, 255]));
+        assert_eq!(rgba8("hsl(120.0, 1.0, 0.5)"), Some([0, 255, 0, 255]));
+        assert_eq!(rgba8("hsl(240.0, 1.0, 0.5)"), Some([0, 0, 255, 255]));
+        assert_eq!(rgba8("hsl(360.0, 1.0, 0.5)"), Some([255, 0, 0, 255]));
+        assert_eq!(rgba8("hsla(0.0, 0.0, 1.0, 0.0)"), Some([255, 255, 255, 0]));
+        assert_eq!(
+            rgba8("TweakColor::hsla(210.0, 0.5, 0.25, 1.0)"),
+            Some([32, 64, 96, 255])
+        );
+        assert_eq!(rgba8("rgb(255, 128)"), None);
+
+        let white: [f32; 4] = TweakColor::hex("#ffffff").into();
+        assert_eq!(white, [1.0; 4]);
+    }
+
+    #[test]
+    fn reports_out_of_range_components() {
+        assert_eq!(
+            TweakColor::try_parse("rgb(256, 128, 0)"),
+            Err(TweakError::OutOfRange {
+                literal: "256".to_string(),
+                ty: "u8",
+            })
+        );
+        assert_eq!(
+            TweakColor::try_parse("rgba(255, 128, -1, 0.5)"),
+            Err(TweakError::OutOfRange {
+                literal: "-1".to_string(),
+                ty: "u8",
+            })
+        );
+    }
+}
@@ -0,0 +1,341 @@
+//! Tweaking of the `glam` vectors, quaternions and matrices, parsed from their constructors and constants.
+//!
+//! Every type supports its constants (e.g `Vec3::ZERO` or `Quat::IDENTITY`), its free constructor function
+//! (e.g `vec3(1.0, 2.0, 3.0)`) and the usual associated constructors:
+//! - vectors: `new`, `splat`, `from_array` and `from` an array.
+//! - quaternions: `from_xyzw`, `from_array`, `from_vec4`, `from_axis_angle`, `from_scaled_axis`,
+//!   `from_rotation_x/y/z`, `from_euler` and `from_rotation_arc`.
+//! - matrices: `from_cols`, `from_cols_array`, `from_cols_array_2d`, `from_diagonal`, and the transforms
+//!   like `from_angle`, `from_quat`, `from_rotation_x/y/z`, `from_axis_angle`, `from_translation` or `from_scale`.
+use crate::tweakable::{path_matches, split_top_level, strip_call, strip_delimiters, try_some};
+use crate::{TweakError, Tweakable};
+use glam::EulerRot;
+
+/// Parses `x` as an item of the glam type `ty`, returning its name and its arguments if it is called.
+/// This is either a constant like `Vec3::ZERO` or a call like `Vec3::new(1.0, 2.0, 3.0)`.
+/// Calling the free `function` (e.g `vec3`) is the same as calling the `equivalent` associated function.
+fn constructor<'a>(
+    x: &'a str,
+    ty: &str,
+    function: &str,
+    equivalent: &'static str,
+) -> Option<(&'a str, Option<Vec<&'a str>>)> {
+    let x = x.trim();
+    if let Some(args) = strip_call(x, function) {
+        return Some((equivalent, Some(split_top_level(args)?)));
+    }
+
+    let (path, args) = match x.find('(') {
+        Some(open) => {
+            let args = split_top_level(strip_delimiters(&x[open..], '(', ')')?)?;
+            (&x[..open], Some(args))
+        }
+        None => (x, None),
+    };
+
+    let (ty_path, name) = path.rsplit_once("::")?;
+    path_matches(ty_path, ty).then_some((name.trim(), args))
+}
+
+/// Parses an argument in a `try_parse`, forwarding its errors.
+macro_rules! arg {
+    ($x: expr) => {
+        try_some!(Tweakable::try_parse($x)?)
+    };
+}
+
+/// Parses each argument, e.g the components given to `Vec3::new`.
+fn parse_args<T: Tweakable, const N: usize>(args: &[&str]) -> Result<Option<[T; N]>, TweakError> {
+    if args.len() != N {
+        return Ok(None);
+    }
+    let mut parsed = Vec::with_capacity(N);
+    for arg in args {
+        parsed.push(arg!(arg));
+    }
+    Ok(parsed.try_into().ok())
+}
+
+/// Parses an array given by reference, e.g to `Mat3::from_cols_array(&[..])`.
+fn parse_array_ref<T: Tweakable>(x: &str) -> Result<Option<T>, TweakError> {
+    T::try_parse(try_some!(x.trim().strip_prefix('&')))
+}
+
+macro_rules! impl_tweakable_glam {
+    ($t: ident, $function: ident => $equivalent: literal, [$($constant: ident)*], $({$($arms: tt)*})+) => {
+        impl Tweakable for glam::$t {
+            fn parse(x: &str) -> Option<Self> {
+                Self::try_parse(x).ok().flatten()
+            }
+
+            fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+                let (name, args) =
+                    try_some!(constructor(x, stringify!($t), stringify!($function), $equivalent));
+                Ok(match (name, args.as_deref()) {
+                    $((stringify!($constant), None) => Some(Self::$constant),)*
+                    $($($arms)*)+
+                    _ => None,
+                })
+            }
+        }
+    };
+}
+
+macro_rules! impl_tweakable_vec {
+    ($($t: ident $function: ident [$($constant: ident)*],)+) => {$(
+        impl_tweakable_glam!($t, $function => "new", [$($constant)*], {
+            ("new", Some(args)) => Some(Self::from_array(try_some!(parse_args(args)?))),
+            ("splat", Some([v])) => Some(Self::splat(arg!(v))),
+            ("from_array" | "from", Some([array])) => Some(Self::from_array(arg!(array))),
+        });
+    )+};
+}
+
+macro_rules! impl_tweakable_quat {
+    ($($t: ident $function: ident,)+) => {$(
+        impl_tweakable_glam!($t, $function => "from_xyzw", [IDENTITY NAN], {
+            ("from_xyzw", Some(args)) => Some(Self::from_array(try_some!(parse_args(args)?))),
+            ("from_array" | "from", Some([array])) => Some(Self::from_array(arg!(array))),
+            ("from_vec4", Some([v])) => Some(Self::from_vec4(arg!(v))),
+            ("from_axis_angle", Some([axis, angle])) => {
+                Some(Self::from_axis_angle(arg!(axis), arg!(angle)))
+            }
+            ("from_scaled_axis", Some([v])) => Some(Self::from_scaled_axis(arg!(v))),
+            ("from_rotation_x", Some([angle])) => Some(Self::from_rotation_x(arg!(angle))),
+            ("from_rotation_y", Some([angle])) => Some(Self::from_rotation_y(arg!(angle))),
+            ("from_rotation_z", Some([angle])) => Some(Self::from_rotation_z(arg!(angle))),
+            ("from_euler", Some([order, a, b, c])) => {
+                Some(Self::from_euler(arg!(order), arg!(a), arg!(b), arg!(c)))
+            }
+            ("from_rotation_arc", Some([from, to])) => {
+                Some(Self::from_rotation_arc(arg!(from), arg!(to)))
+            }
+        });
+    )+};
+}
+
+/// The transforms specific to each matrix size are given as extra match arms.
+macro_rules! impl_tweakable_mat {
+    ($($t: ident $function: ident [$($col: ident)+]),+ => $transforms: tt) => {$(
+        impl_tweakable_glam!($t, $function => "from_cols", [ZERO IDENTITY NAN], {
+            ("from_cols", Some([$($col),+])) => Some(Self::from_cols($(arg!($col)),+)),
+            ("from_cols_array", Some([array])) => Some(Self::from_cols_array(&try_some!(parse_array_ref(array)?))),
+            ("from_cols_array_2d", Some([array])) => Some(Self::from_cols_array_2d(&try_some!(parse_array_ref(array)?))),
+            ("from_diagonal", Some([diagonal])) => Some(Self::from_diagonal(arg!(diagonal))),
+        } $transforms);
+    )+};
+}
+
+impl_tweakable_vec!(
+    Vec2 vec2 [ZERO ONE NEG_ONE MIN MAX NAN INFINITY NEG_INFINITY X Y NEG_X NEG_Y],
+    Vec3 vec3 [ZERO ONE NEG_ONE MIN MAX NAN INFINITY NEG_INFINITY X Y Z NEG_X NEG_Y NEG_Z],
+    Vec3A vec3a [ZERO ONE NEG_ONE MIN MAX NAN INFINITY NEG_INFINITY X Y Z NEG_X NEG_Y NEG_Z],
+    Vec4 vec4 [ZERO ONE NEG_ONE MIN MAX NAN INFINITY NEG_INFINITY X Y Z W NEG_X NEG_Y NEG_Z NEG_W],
+    DVec2 dvec2 [ZERO ONE NEG_ONE MIN MAX NAN INFINITY NEG_INFINITY X Y NEG_X NEG_Y],
+    DVec3 dvec3 [ZERO ONE NEG_ONE MIN MAX NAN INFINITY NEG_INFINITY X Y Z NEG_X NEG_Y NEG_Z],
+    DVec4 dvec4 [ZERO ONE NEG_ONE MIN MAX NAN INFINITY NEG_INFINITY X Y Z W NEG_X NEG_Y NEG_Z NEG_W],
+    IVec2 ivec2 [ZERO ONE NEG_ONE MIN MAX X Y NEG_X NEG_Y],
+    IVec3 ivec3 [ZERO ONE NEG_ONE MIN MAX X Y Z NEG_X NEG_Y NEG_Z],
+    IVec4 ivec4 [ZERO ONE NEG_ONE MIN MAX X Y Z W NEG_X NEG_Y NEG_Z NEG_W],
+    UVec2 uvec2 [ZERO ONE MIN MAX X Y],
+    UVec3 uvec3 [ZERO ONE MIN MAX X Y Z],
+    UVec4 uvec4 [ZERO ONE MIN MAX X Y Z W],
+);
+
+impl_tweakable_quat!(
+    Quat quat,
+    DQuat dquat,
+);
+
+impl_tweakable_mat!(
+    Mat2 mat2 [x_axis y_axis],
+    DMat2 dmat2 [x_axis y_axis] => {
+        ("from_angle", Some([angle])) => Some(Self::from_angle(arg!(angle))),
+        ("from_scale_angle", Some([scale, angle])) => {
+            Some(Self::from_scale_angle(arg!(scale), arg!(angle)))
+        }
+    }
+);
+
+impl_tweakable_mat!(
+    Mat3 mat3 [x_axis y_axis z_axis],
+    Mat3A mat3a [x_axis y_axis z_axis],
+    DMat3 dmat3 [x_axis y_axis z_axis] => {
+        ("from_quat", Some([rotation])) => Some(Self::from_quat(arg!(rotation))),
+        ("from_axis_angle", Some([axis, angle])) => {
+            Some(Self::from_axis_angle(arg!(axis), arg!(angle)))
+        }
+        ("from_euler", Some([order, a, b, c])) => {
+            Some(Self::from_euler(arg!(order), arg!(a), arg!(b), arg!(c)))
+        }
+        ("from_rotation_x", Some([angle])) => Some(Self::from_rotation_x(arg!(angle))),
+        ("from_rotation_y", Some([angle])) => Some(Self::from_rotation_y(arg!(angle))),
+        ("from_rotation_z", Some([angle])) => Some(Self::from_rotation_z(arg!(angle))),
+        ("from_angle", Some([angle])) => Some(Self::from_angle(arg!(angle))),
+        ("from_translation", Some([translation])) => {
+            Some(Self::from_translation(arg!(translation)))
+        }
+        ("from_scale", Some([scale])) => Some(Self::from_scale(arg!(scale))),
+    }
+);
+
+impl_tweakable_mat!(
+    Mat4 mat4 [x_axis y_axis z_axis w_axis],
+    DMat4 dmat4 [x_axis y_axis z_axis w_axis] => {
+        ("from_quat", Some([rotation])) => Some(Self::from_quat(arg!(rotation))),
+        ("from_axis_angle", Some([axis, angle])) => {
+            Some(Self::from_axis_angle(arg!(axis), arg!(angle)))
+        }
+        ("from_euler", Some([order, a, b, c])) => {
+            Some(Self::from_euler(arg!(order), arg!(a), arg!(b), arg!(c)))
+        }
+        ("from_rotation_x", Some([angle])) => Some(Self::from_rotation_x(arg!(angle))),
+        ("from_rotation_y", Some([angle])) => Some(Self::from_rotation_y(arg!(angle))),
+        ("from_rotation_z", Some([angle])) => Some(Self::from_rotation_z(arg!(angle))),
+        ("from_translation", Some([translation])) => {
+            Some(Self::from_translation(arg!(translation)))
+        }
+        ("from_scale", Some([scale])) => Some(Self::from_scale(arg!(scale))),
+        ("from_rotation_translation", Some([rotation, translation])) => {
+            Some(Self::from_rotation_translation(arg!(rotation), arg!(translation)))
+        }
+        ("from_scale_rotation_translation", Some([scale, rotation, translation])) => {
+            Some(Self::from_scale_rotation_translation(
+                arg!(scale),
+                arg!(rotation),
+                arg!(translation),
+            ))
+        }
+    }
+);
+
+macro_rules! impl_tweakable_euler_rot {
+    ($($variant: ident)+) => {
+        /// Supports the rotation orders used by `from_euler`, like `EulerRot::XYZ`.
+        impl Tweakable for EulerRot {
+            fn parse(x: &str) -> Option<Self> {
+                let (path, variant) = x.trim().rsplit_once("::")?;
+                if !path_matches(path, "EulerRot") {
+                    return None;
+                }
+                match variant.trim() {
+                    $(stringify!($variant) => Some(EulerRot::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_tweakable_euler_rot!(
+    ZYX ZXY YXZ YZX XYZ XZY ZYZ ZXZ YXY YZY XYX XZX
+    ZYXEx ZXYEx YXZEx YZXEx XYZEx XZYEx ZYZEx ZXZEx YXYEx YZYEx XYXEx XZXEx
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::*;
+
+    #[test]
+    fn parses_vectors() {
+        let v = vec3(1.0, 2.0, -3.0);
+        assert_eq!(Vec3::parse("vec3(1.0, 2.0, -3.0)"), Some(v));
+        assert_eq!(Vec3::parse("glam::vec3(1.0, 2.0, -3.0)"), Some(v));
+        assert_eq!(Vec3::parse("Vec3::new(1.0, 2.0, -3.0)"), Some(v));
+        assert_eq!(Vec3::parse("glam::Vec3::new(1.0, 2.0, -3.0)"), Some(v));
+        assert_eq!(Vec3::parse("Vec3::from([1.0, 2.0, -3.0])"), Some(v));
+        assert_eq!(Vec3::parse("Vec3::splat(0.5)"), Some(Vec3::splat(0.5)));
+        assert_eq!(Vec3::parse("Vec3::ZERO"), Some(Vec3::ZERO));
+        assert_eq!(Vec3::parse("glam::Vec3::NEG_Z"), Some(Vec3::NEG_Z));
+        assert_eq!(
+            Vec3A::parse("vec3a(1.0, 2.0, 3.0)"),
+            Some(vec3a(1.0, 2.0, 3.0))
+        );
+        assert_eq!(DVec2::parse("dvec2(1.0, 2.0)"), Some(dvec2(1.0, 2.0)));
+        assert_eq!(IVec2::parse("IVec2::new(1, -2)"), Some(ivec2(1, -2)));
+        assert_eq!(UVec4::parse("UVec4::W"), Some(UVec4::W));
+
+        // Other types and constants
+        assert_eq!(Vec3::parse("Vec3::W"), None);
+        assert_eq!(Vec3::parse("Vec3A::ZERO"), None);
+        assert_eq!(Vec3::parse("vec3a(1.0, 2.0, 3.0)"), None);
+    }
+
+    #[test]
+    fn parses_quaternions() {
+        assert_eq!(Quat::parse("Quat::IDENTITY"), Some(Quat::IDENTITY));
+        assert_eq!(
+            Quat::parse("quat(0.0, 0.0, 0.0, 1.0)"),
+            Some(Quat::IDENTITY)
+        );
+        assert_eq!(
+            Quat::parse("Quat::from_rotation_y(0.5)"),
+            Some(Quat::from_rotation_y(0.5))
+        );
+        assert_eq!(
+            Quat::parse("Quat::from_axis_angle(Vec3::Y, 1.0)"),
+            Some(Quat::from_axis_angle(Vec3::Y, 1.0))
+        );
+        assert_eq!(
+            Quat::parse("Quat::from_euler(EulerRot::YXZ, 1.0, 0.5, 0.0)"),
+            Some(Quat::from_euler(EulerRot::YXZ, 1.0, 0.5, 0.0))
+        );
+    }
+
+    #[test]
+    fn parses_matrices() {
+        assert_eq!(Mat4::parse("Mat4::IDENTITY"), Some(Mat4::IDENTITY));
+        assert_eq!(
+            Mat4::parse("Mat4::from_translation(vec3(1.0, 2.0, 3.0))"),
+            Some(Mat4::from_translation(vec3(1.0, 2.0, 3.0)))
+        );
+        assert_eq!(Mat2::parse("mat2(Vec2::X, Vec2::Y)"), Some(Mat2::IDENTITY));
+        assert_eq!(
+            Mat2::parse("Mat2::from_cols(Vec2::X, vec2(1.0, 2.0))"),
+            Some(Mat2::from_cols(Vec2::X, vec2(1.0, 2.0)))
+        );
+        assert_eq!(
+            Mat2::parse("Mat2::from_cols_array(&[1.0, 0.0, 0.0, 1.0])"),
+            Some(Mat2::IDENTITY)
+        );
+        assert_eq!(
+            Mat3::parse("Mat3::from_diagonal(Vec3::splat(2.0))"),
+            Some(Mat3::from_diagonal(Vec3::splat(2.0)))
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_arity() {
+        assert_eq!(Vec3::try_parse("Vec3::new(1.0, 2.0)"), Ok(None));
+        assert_eq!(Vec3::try_parse("vec3(1.0, 2.0, 3.0, 4.0)"), Ok(None));
+        assert_eq!(Vec3::try_parse("Vec3::from([1.0, 2.0])"), Ok(None));
+        assert_eq!(Vec3::try_parse("Vec3::splat(1.0, 2.0)"), Ok(None));
+        assert_eq!(Quat::try_parse("quat(0.0, 0.0, 1.0)"), Ok(None));
+        assert_eq!(Mat2::try_parse("mat2(Vec2::X)"), Ok(None));
+        assert_eq!(
+            Mat2::try_parse("Mat2::from_cols_array(&[1.0, 0.0, 0.0])"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn forwards_component_errors() {
+        let err = || {
+            Err(TweakError::OutOfRange {
+                literal: "3_000_000_000".to_string(),
+                ty: "i32",
+            })
+        };
+        assert_eq!(IVec2::try_parse("ivec2(1, 3_000_000_000)"), err());
+        assert_eq!(IVec2::try_parse("IVec2::splat(3_000_000_000)"), err());
+        assert_eq!(IVec2::try_parse("IVec2::new(1, x)"), Ok(None));
+        assert_eq!(
+            Vec2::try_parse("vec2(1.0, 1e999)"),
+            Err(TweakError::OutOfRange {
+                literal: "1e999".to_string(),
+                ty: "f32",
+            })
+        );
+    }
+}
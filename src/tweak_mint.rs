@@ -0,0 +1,143 @@
+//! Tweaking of the `mint` vectors, points, quaternions and matrices.
+//!
+//! They are written as struct literals like `Vector3 { x: 1.0, y: 2.0, z: 3.0 }`, or converted from arrays
+//! like `Vector3::from([1.0, 2.0, 3.0])`. Matrices can be converted from both their nested and flat arrays.
+use crate::tweakable::derive_helpers::{field, named_fields};
+use crate::tweakable::{strip_call, try_some};
+use crate::{TweakError, Tweakable};
+
+macro_rules! impl_tweakable_mint {
+    ($($t: ident { $($field: ident),+ } from $($array: ty)|+;)+) => {$(
+        impl<T: Tweakable> Tweakable for mint::$t<T> {
+            fn parse(x: &str) -> Option<Self> {
+                Self::try_parse(x).ok().flatten()
+            }
+
+            fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+                if let Some(array) = strip_call(x, concat!(stringify!($t), "::from")) {
+                    $(
+                        if let Some(array) = <$array>::try_parse(array)? {
+                            return Ok(Some(Self::from(array)));
+                        }
+                    )+
+                    return Ok(None);
+                }
+
+                let fields = try_some!(named_fields(x, stringify!($t), None));
+                if fields.len() != [$(stringify!($field)),+].len() {
+                    return Ok(None);
+                }
+                Ok(Some(Self {
+                    $($field: try_some!(Tweakable::try_parse(try_some!(field(&fields, stringify!($field))))?),)+
+                }))
+            }
+        }
+    )+};
+}
+
+impl_tweakable_mint!(
+    Vector2 { x, y } from [T; 2];
+    Vector3 { x, y, z } from [T; 3];
+    Vector4 { x, y, z, w } from [T; 4];
+    Point2 { x, y } from [T; 2];
+    Point3 { x, y, z } from [T; 3];
+    Quaternion { v, s } from [T; 4];
+
+    RowMatrix2 { x, y } from [[T; 2]; 2] | [T; 4];
+    RowMatrix2x3 { x, y } from [[T; 3]; 2] | [T; 6];
+    RowMatrix2x4 { x, y } from [[T; 4]; 2] | [T; 8];
+    RowMatrix3x2 { x, y, z } from [[T; 2]; 3] | [T; 6];
+    RowMatrix3 { x, y, z } from [[T; 3]; 3] | [T; 9];
+    RowMatrix3x4 { x, y, z } from [[T; 4]; 3] | [T; 12];
+    RowMatrix4x2 { x, y, z, w } from [[T; 2]; 4] | [T; 8];
+    RowMatrix4x3 { x, y, z, w } from [[T; 3]; 4] | [T; 12];
+    RowMatrix4 { x, y, z, w } from [[T; 4]; 4] | [T; 16];
+
+    ColumnMatrix2 { x, y } from [[T; 2]; 2] | [T; 4];
+    ColumnMatrix2x3 { x, y, z } from [[T; 2]; 3] | [T; 6];
+    ColumnMatrix2x4 { x, y, z, w } from [[T; 2]; 4] | [T; 8];
+    ColumnMatrix3x2 { x, y } from [[T; 3]; 2] | [T; 6];
+    ColumnMatrix3 { x, y, z } from [[T; 3]; 3] | [T; 9];
+    ColumnMatrix3x4 { x, y, z, w } from [[T; 3]; 4] | [T; 12];
+    ColumnMatrix4x2 { x, y } from [[T; 4]; 2] | [T; 8];
+    ColumnMatrix4x3 { x, y, z } from [[T; 4]; 3] | [T; 12];
+    ColumnMatrix4 { x, y, z, w } from [[T; 4]; 4] | [T; 16];
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mint::*;
+
+    #[test]
+    fn parses_vectors() {
+        let v = Vector3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        assert_eq!(
+            Vector3::parse("Vector3 { x: 1.0, y: 2.0, z: 3.0 }"),
+            Some(v)
+        );
+        assert_eq!(
+            Vector3::parse("mint::Vector3 { z: 3.0, x: 1.0, y: 2.0 }"),
+            Some(v)
+        );
+        assert_eq!(Vector3::parse("Vector3::from([1.0, 2.0, 3.0])"), Some(v));
+        assert_eq!(
+            Point2::parse("Point2::from([1, 2])"),
+            Some(Point2 { x: 1, y: 2 })
+        );
+    }
+
+    #[test]
+    fn parses_quaternions_and_matrices() {
+        assert_eq!(
+            Quaternion::parse("Quaternion { v: Vector3::from([0.0, 0.0, 0.0]), s: 1.0 }"),
+            Some(Quaternion::from([0.0, 0.0, 0.0, 1.0]))
+        );
+        let identity = ColumnMatrix2::from([[1.0, 0.0], [0.0, 1.0]]);
+        assert_eq!(
+            ColumnMatrix2::parse("ColumnMatrix2::from([[1.0, 0.0], [0.0, 1.0]])"),
+            Some(identity)
+        );
+        assert_eq!(
+            ColumnMatrix2::parse("ColumnMatrix2::from([1.0, 0.0, 0.0, 1.0])"),
+            Some(identity)
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_arity() {
+        assert_eq!(
+            Vector3::<f32>::try_parse("Vector3 { x: 1.0, y: 2.0 }"),
+            Ok(None)
+        );
+        assert_eq!(
+            Vector3::<f32>::try_parse("Vector3 { x: 1.0, y: 2.0, z: 3.0, w: 4.0 }"),
+            Ok(None)
+        );
+        assert_eq!(
+            Vector3::<f32>::try_parse("Vector3::from([1.0, 2.0])"),
+            Ok(None)
+        );
+        assert_eq!(
+            ColumnMatrix2::<f32>::try_parse("ColumnMatrix2::from([1.0, 0.0, 0.0])"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn forwards_component_errors() {
+        let err = || {
+            Err(TweakError::OutOfRange {
+                literal: "300".to_string(),
+                ty: "u8",
+            })
+        };
+        assert_eq!(Vector2::<u8>::try_parse("Vector2 { x: 1, y: 300 }"), err());
+        assert_eq!(Vector2::<u8>::try_parse("Vector2::from([1, 300])"), err());
+        assert_eq!(Vector2::<u8>::try_parse("Vector2 { x: 1, y: z }"), Ok(None));
+    }
+}
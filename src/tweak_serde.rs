@@ -0,0 +1,753 @@
+//! Tweaking of any type implementing `serde::Deserialize`, parsed from its Rust literal syntax.
+use crate::tweakable::derive_helpers::{braced_fields, named_fields, tuple_fields};
+use crate::tweakable::{
+    owned_str_literal, parse_str_literal, path_matches, scan_top_level, split_repeat,
+    split_top_level, strip_call, strip_delimiters, IntegerLiteral, StrKind,
+};
+use crate::{TweakError, Tweakable};
+use serde::de::value::StrDeserializer;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use std::fmt;
+
+/// Makes any type implementing `Deserialize` tweakable, by parsing the Rust literal given to the wrapper.
+/// Missing fields in a struct update like `..Default::default()` are left to serde, e.g using `#[serde(default)]`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use inline_tweak::TweakSerde;
+///
+/// #[derive(Clone, serde::Deserialize)]
+/// struct Config {
+///     speed: f32,
+///     names: Vec<String>,
+/// }
+///
+/// loop {
+///     let config = inline_tweak::tweak!(TweakSerde(Config {
+///         speed: 2.0,
+///         names: vec!["a".to_string()],
+///     }))
+///     .0;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TweakSerde<T>(pub T);
+
+impl<T> TweakSerde<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> core::ops::Deref for TweakSerde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Supports `TweakSerde(value)` and the bare `value`.
+/// The errors of the `Deserialize` implementation, like an unknown variant or a missing field, are reported.
+impl<T: DeserializeOwned + Clone + Send + 'static> Tweakable for TweakSerde<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        let x = x.trim();
+        let inner = strip_call(x, "TweakSerde").unwrap_or(x);
+        match T::deserialize(LiteralDeserializer(inner)) {
+            Ok(v) => Ok(Some(TweakSerde(v))),
+            Err(Error::Syntax(_)) => Ok(None),
+            Err(Error::Tweak(e)) => Err(e),
+            Err(Error::Custom(reason)) => Err(TweakError::Invalid {
+                literal: x.to_string(),
+                ty: std::any::type_name::<T>(),
+                reason,
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Error {
+    /// The text is not a literal of the expected shape, so the default value is used.
+    Syntax(String),
+    /// A literal rejected by its `Tweakable` implementation, like `300` for a `u8`.
+    Tweak(TweakError),
+    /// An error of the `Deserialize` implementation.
+    Custom(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(msg) | Error::Custom(msg) => f.write_str(msg),
+            Error::Tweak(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Custom(msg.to_string())
+    }
+}
+
+impl From<TweakError> for Error {
+    fn from(e: TweakError) -> Self {
+        Error::Tweak(e)
+    }
+}
+
+fn invalid(x: &str) -> Error {
+    Error::Syntax(format!("invalid literal `{}`", x.trim()))
+}
+
+/// The largest repeat count of a sequence literal like `[a; n]`, as the visitor collects the elements.
+/// Arrays and tuples are not limited, their count is checked against their length instead.
+const MAX_REPEAT: usize = 1 << 20;
+
+/// Deserializes the source text of a Rust literal.
+struct LiteralDeserializer<'a>(&'a str);
+
+impl<'a> LiteralDeserializer<'a> {
+    /// Returns the elements of a sequence literal like `[a, b]`, `vec![a, b]`, `[a; n]` or `Vec::from([a, b])`.
+    /// The count of a repeat expression must be `len` if given, or at most [`MAX_REPEAT`].
+    fn seq(&self, len: Option<usize>) -> Result<Elements<&'a str>, Error> {
+        let x = self.0.trim();
+        let list = x.strip_prefix("vec!").or_else(|| from_call(x)).unwrap_or(x);
+        let inner = strip_delimiters(list, '[', ']').ok_or_else(|| invalid(x))?;
+
+        if let Some((elem, count)) = split_repeat(inner) {
+            let count = usize::try_parse(count)?.ok_or_else(|| invalid(x))?;
+            if len.map_or(count > MAX_REPEAT, |len| len != count) {
+                return Err(invalid(x));
+            }
+            return Ok(Elements::Repeat(elem, count));
+        }
+        split_top_level(inner)
+            .map(Elements::from)
+            .ok_or_else(|| invalid(x))
+    }
+
+    /// Returns the `(key, value)` entries of a map literal like `HashMap::from([(k, v)])` or `[(k, v)]`.
+    fn entries(&self) -> Result<Elements<(&'a str, &'a str)>, Error> {
+        let entry = |entry: &'a str| match split_top_level(strip_delimiters(entry, '(', ')')?)?[..]
+        {
+            [key, value] => Some((key, value)),
+            _ => None,
+        };
+        match self.seq(None)? {
+            Elements::List(entries) => entries
+                .map(entry)
+                .collect::<Option<Vec<_>>>()
+                .map(Elements::from),
+            Elements::Repeat(elem, count) => {
+                entry(elem).map(|entry| Elements::Repeat(entry, count))
+            }
+        }
+        .ok_or_else(|| invalid(self.0))
+    }
+}
+
+/// The elements of a sequence literal. A repeat expression like `[a; n]` yields its element `n` times
+/// without storing the copies, as `n` can be arbitrarily large.
+enum Elements<T> {
+    List(std::vec::IntoIter<T>),
+    Repeat(T, usize),
+}
+
+impl<T> From<Vec<T>> for Elements<T> {
+    fn from(list: Vec<T>) -> Self {
+        Elements::List(list.into_iter())
+    }
+}
+
+impl<T: Copy> Iterator for Elements<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self {
+            Elements::List(list) => list.next(),
+            Elements::Repeat(_, 0) => None,
+            Elements::Repeat(elem, count) => {
+                *count -= 1;
+                Some(*elem)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self {
+            Elements::List(list) => list.len(),
+            Elements::Repeat(_, count) => *count,
+        };
+        (len, Some(len))
+    }
+}
+
+impl<T: Copy> ExactSizeIterator for Elements<T> {}
+
+/// Returns the argument of a conversion like `HashMap::from([..])` or `String::from("..")`.
+fn from_call(x: &str) -> Option<&str> {
+    let open = x.find('(')?;
+    if !x[..open].trim_end().ends_with("::from") {
+        return None;
+    }
+    strip_delimiters(&x[open..], '(', ')')
+}
+
+/// Splits an enum literal like `Enum::Variant(a, b)` into the path before the variant, its name and its payload.
+fn split_variant(x: &str) -> Option<(&str, &str, &str)> {
+    let x = x.trim();
+    let mut payload_start = x.len();
+    scan_top_level(x, |i, c, depth| {
+        let found = depth == 0 && (c == '(' || c == '{');
+        if found {
+            payload_start = i;
+        }
+        found
+    })?;
+
+    let path = x[..payload_start].trim_end();
+    let (prefix, variant) = path.rsplit_once("::").unwrap_or(("", path));
+    let variant = variant.trim();
+    let variant = variant.strip_prefix("r#").unwrap_or(variant);
+    Some((prefix.trim(), variant, &x[payload_start..]))
+}
+
+/// Whether the path before an enum variant, e.g `Enum` in `Enum::Variant`, names the enum `name`.
+/// Variants may also be written alone, e.g when imported with `use Enum::*`.
+fn names_enum(prefix: &str, name: &str) -> bool {
+    let last = prefix.rsplit("::").next().unwrap_or(prefix).trim();
+    prefix.is_empty() || last == "Self" || last.strip_prefix("r#").unwrap_or(last) == name
+}
+
+macro_rules! deserialize_tweakable {
+    ($($method: ident => $t: ty, $visit: ident;)+) => {
+        $(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.$visit(<$t>::try_parse(self.0)?.ok_or_else(|| invalid(self.0))?)
+        }
+        )+
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for LiteralDeserializer<'a> {
+    type Error = Error;
+
+    /// Guesses the type of the literal, used by self-describing types like `serde_json::Value`.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let x = self.0.trim();
+
+        if let Some(v) = bool::parse(x) {
+            return visitor.visit_bool(v);
+        }
+        if let Some(lit) = IntegerLiteral::parse(x) {
+            if !lit.suffix.is_some_and(|suffix| suffix.starts_with('f')) {
+                return match (lit.negative, lit.magnitude) {
+                    (false, Some(v)) => match u64::try_from(v) {
+                        Ok(v) => visitor.visit_u64(v),
+                        Err(_) => visitor.visit_u128(v),
+                    },
+                    (true, Some(_)) => {
+                        let v = i128::parse(x).ok_or_else(|| invalid(x))?;
+                        match i64::try_from(v) {
+                            Ok(v) => visitor.visit_i64(v),
+                            Err(_) => visitor.visit_i128(v),
+                        }
+                    }
+                    (_, None) => Err(invalid(x)),
+                };
+            }
+        }
+        match f64::try_parse(x) {
+            Ok(Some(v)) => return visitor.visit_f64(v),
+            Ok(None) => {}
+            // `f32` suffixed literals
+            Err(e) => return visitor.visit_f32(f32::try_parse(x).ok().flatten().ok_or(e)?),
+        }
+        if x.starts_with('\'') {
+            return self.deserialize_char(visitor);
+        }
+        if let Some(v) = owned_str_literal(x) {
+            return visitor.visit_string(v);
+        }
+        if let Some(bytes) = parse_str_literal(x, StrKind::Byte) {
+            return visitor.visit_byte_buf(bytes);
+        }
+        if x.starts_with('[') || x.starts_with("vec!") {
+            return self.deserialize_seq(visitor);
+        }
+        if x.starts_with('(') {
+            return match strip_delimiters(x, '(', ')') {
+                Some(inner) if inner.trim().is_empty() => visitor.visit_unit(),
+                _ => self.deserialize_tuple(0, visitor),
+            };
+        }
+        if path_matches(x, "None") || strip_call(x, "Some").is_some() {
+            return self.deserialize_option(visitor);
+        }
+
+        // Struct or tuple struct literals, which are named
+        let (_, _, payload) = split_variant(x).ok_or_else(|| invalid(x))?;
+        if payload.starts_with('{') {
+            let fields = braced_fields(payload).ok_or_else(|| invalid(x))?;
+            return visitor.visit_map(MapDeserializer::fields(fields));
+        }
+        if payload.starts_with('(') {
+            let fields =
+                split_top_level(strip_delimiters(payload, '(', ')').ok_or_else(|| invalid(x))?)
+                    .ok_or_else(|| invalid(x))?;
+            return visitor.visit_seq(SeqDeserializer(fields.into()));
+        }
+        visitor.visit_unit()
+    }
+
+    deserialize_tweakable! {
+        deserialize_bool => bool, visit_bool;
+        deserialize_i8 => i8, visit_i8;
+        deserialize_i16 => i16, visit_i16;
+        deserialize_i32 => i32, visit_i32;
+        deserialize_i64 => i64, visit_i64;
+        deserialize_i128 => i128, visit_i128;
+        deserialize_u8 => u8, visit_u8;
+        deserialize_u16 => u16, visit_u16;
+        deserialize_u32 => u32, visit_u32;
+        deserialize_u64 => u64, visit_u64;
+        deserialize_u128 => u128, visit_u128;
+        deserialize_f32 => f32, visit_f32;
+        deserialize_f64 => f64, visit_f64;
+        deserialize_char => char, visit_char;
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(owned_str_literal(self.0).ok_or_else(|| invalid(self.0))?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let bytes = parse_str_literal(self.0, StrKind::Byte).ok_or_else(|| invalid(self.0))?;
+        visitor.visit_byte_buf(bytes)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if path_matches(self.0, "None") {
+            return visitor.visit_none();
+        }
+        match split_top_level(strip_call(self.0, "Some").ok_or_else(|| invalid(self.0))?).as_deref()
+        {
+            Some(&[inner]) => visitor.visit_some(LiteralDeserializer(inner)),
+            _ => Err(invalid(self.0)),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match strip_delimiters(self.0, '(', ')') {
+            Some(inner) if inner.trim().is_empty() => visitor.visit_unit(),
+            _ => Err(invalid(self.0)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if !path_matches(self.0, name) && self.0.trim() != "Self" {
+            return self.deserialize_unit(visitor);
+        }
+        visitor.visit_unit()
+    }
+
+    /// Newtypes can also be written as their inner value.
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match tuple_fields(self.0, name, None).as_deref() {
+            Some(&[inner]) => visitor.visit_newtype_struct(LiteralDeserializer(inner)),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    /// Byte string literals are also accepted, e.g for a `Vec<u8>`.
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if let Some(bytes) = parse_str_literal(self.0, StrKind::Byte) {
+            return visitor.visit_seq(de::value::SeqDeserializer::new(bytes.into_iter()));
+        }
+        visitor.visit_seq(SeqDeserializer(self.seq(None)?))
+    }
+
+    /// Tuples are written with parentheses, and arrays (which serde also deserializes as tuples) with brackets.
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        let elems = match strip_delimiters(self.0, '(', ')') {
+            Some(inner) => split_top_level(inner)
+                .ok_or_else(|| invalid(self.0))?
+                .into(),
+            None => self.seq(Some(len))?,
+        };
+        visitor.visit_seq(SeqDeserializer(elems))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let fields = tuple_fields(self.0, name, None).ok_or_else(|| invalid(self.0))?;
+        visitor.visit_seq(SeqDeserializer(fields.into()))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(MapDeserializer::entries(self.entries()?))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let fields = named_fields(self.0, name, None).ok_or_else(|| invalid(self.0))?;
+        visitor.visit_map(MapDeserializer::fields(fields))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let (prefix, variant, payload) = split_variant(self.0).ok_or_else(|| invalid(self.0))?;
+        if !names_enum(prefix, name) {
+            return Err(invalid(self.0));
+        }
+        visitor.visit_enum(EnumDeserializer {
+            x: self.0,
+            variant,
+            payload,
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_str(self.0.trim())
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct SeqDeserializer<'a>(Elements<&'a str>);
+
+impl<'de, 'a> de::SeqAccess<'de> for SeqDeserializer<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.0
+            .next()
+            .map(|elem| seed.deserialize(LiteralDeserializer(elem)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// Deserializes the fields of a struct literal, or the entries of a map literal.
+struct MapDeserializer<'a> {
+    entries: Elements<(&'a str, &'a str)>,
+    value: Option<&'a str>,
+    /// Struct fields are identifiers, while the keys of a map are literals.
+    keys_are_fields: bool,
+}
+
+impl<'a> MapDeserializer<'a> {
+    fn fields(fields: Vec<(&'a str, &'a str)>) -> Self {
+        MapDeserializer {
+            entries: fields.into(),
+            value: None,
+            keys_are_fields: true,
+        }
+    }
+
+    fn entries(entries: Elements<(&'a str, &'a str)>) -> Self {
+        MapDeserializer {
+            entries,
+            value: None,
+            keys_are_fields: false,
+        }
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for MapDeserializer<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+
+        if self.keys_are_fields {
+            let key: StrDeserializer<Error> = key.into_deserializer();
+            seed.deserialize(key).map(Some)
+        } else {
+            seed.deserialize(LiteralDeserializer(key)).map(Some)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Custom("value requested before key".to_string()))?;
+        seed.deserialize(LiteralDeserializer(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Deserializes an enum literal like `Enum::Variant`, `Enum::Variant(a, b)` or `Enum::Variant { a: 1 }`.
+struct EnumDeserializer<'a> {
+    x: &'a str,
+    variant: &'a str,
+    /// The payload of the variant including its delimiters, empty for unit variants.
+    payload: &'a str,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumDeserializer<'a> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let variant: StrDeserializer<Error> = self.variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, self))
+    }
+}
+
+impl<'a> EnumDeserializer<'a> {
+    fn tuple_payload(&self) -> Result<Vec<&'a str>, Error> {
+        strip_delimiters(self.payload, '(', ')')
+            .and_then(split_top_level)
+            .ok_or_else(|| invalid(self.x))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for EnumDeserializer<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        if self.payload.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid(self.x))
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self.tuple_payload()?[..] {
+            [inner] => seed.deserialize(LiteralDeserializer(inner)),
+            _ => Err(invalid(self.x)),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(SeqDeserializer(self.tuple_payload()?.into()))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let fields = braced_fields(self.payload).ok_or_else(|| invalid(self.x))?;
+        visitor.visit_map(MapDeserializer::fields(fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    enum Difficulty {
+        Easy,
+        Hard { enemies: u32 },
+        Custom(f32, f32),
+        Named(String),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Meters(f32);
+
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    struct Unit;
+
+    #[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+    #[serde(default)]
+    struct Config {
+        speed: f32,
+        names: Vec<String>,
+        difficulty: Option<Difficulty>,
+        scores: HashMap<String, u8>,
+        cell: [u8; 2],
+        pair: (i8, char),
+        length: Option<Meters>,
+        bytes: Vec<u8>,
+    }
+
+    fn parse<T: DeserializeOwned + Clone + Send + 'static>(x: &str) -> Option<T> {
+        TweakSerde::<T>::parse(x).map(TweakSerde::into_inner)
+    }
+
+    #[test]
+    fn parses_structs() {
+        let config: Config = parse(
+            r#"TweakSerde(Config {
+                speed: 2.0,
+                names: vec!["walk".to_string(), String::from("r,un")],
+                difficulty: Some(Difficulty::Hard { enemies: 3 }),
+                cell: [1, 2],
+                pair: (-3, 'x'),
+                length: Some(Meters(1.5)),
+                ..Default::default()
+            })"#,
+        )
+        .unwrap();
+        assert_eq!(config.speed, 2.0);
+        assert_eq!(config.names, ["walk", "r,un"]);
+        assert_eq!(config.difficulty, Some(Difficulty::Hard { enemies: 3 }));
+        assert_eq!(config.cell, [1, 2]);
+        assert_eq!(config.pair, (-3, 'x'));
+        assert_eq!(config.length, Some(Meters(1.5)));
+
+        assert_eq!(parse("Meters(2.0)"), Some(Meters(2.0)));
+        assert_eq!(parse("2.0"), Some(Meters(2.0)));
+        assert_eq!(parse("Unit"), Some(Unit));
+        assert_eq!(parse::<Config>(r#"Config { speed: "fast" }"#), None);
+        assert_eq!(parse::<Config>("Settings { speed: 1.0 }"), None);
+    }
+
+    #[test]
+    fn parses_enums() {
+        assert_eq!(parse("Difficulty::Easy"), Some(Difficulty::Easy));
+        assert_eq!(parse("Easy"), Some(Difficulty::Easy));
+        assert_eq!(parse("Self::Easy"), Some(Difficulty::Easy));
+        assert_eq!(
+            parse("crate::Difficulty::Custom(1.0, 2.0)"),
+            Some(Difficulty::Custom(1.0, 2.0))
+        );
+        assert_eq!(
+            parse(r#"Difficulty::Named("x")"#),
+            Some(Difficulty::Named("x".to_string()))
+        );
+        assert_eq!(parse::<Difficulty>("Mode::Easy"), None);
+        assert_eq!(parse::<Difficulty>("Difficulty::Nope"), None);
+        assert!(matches!(
+            TweakSerde::<Difficulty>::try_parse("Difficulty::Nope"),
+            Err(TweakError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_sequences() {
+        assert_eq!(parse("vec![1, 2, 3]"), Some(vec![1u8, 2, 3]));
+        assert_eq!(parse("Vec::from([1, 2])"), Some(vec![1u8, 2]));
+        assert_eq!(parse("vec![1; 3]"), Some(vec![1u8, 1, 1]));
+        assert_eq!(parse("[7; 2]"), Some([7u8; 2]));
+        assert_eq!(parse::<[u8; 2]>("[7; 3]"), None);
+        assert_eq!(parse::<Vec<u8>>("vec![0; 18446744073709551615]"), None);
+        assert_eq!(parse("(1, 'a', \"b\")"), Some((1u8, 'a', "b".to_string())));
+    }
+
+    #[test]
+    fn parses_maps() {
+        let scores: HashMap<String, u8> =
+            parse(r#"HashMap::from([("a".to_string(), 1), ("b".into(), 2u8)])"#).unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores["b"], 2);
+        assert_eq!(parse("[(1, 2.0)]"), Some(HashMap::from([(1u8, 2.0f32)])));
+    }
+
+    #[test]
+    fn parses_byte_strings() {
+        let config: Config = parse(r#"Config { bytes: b"ab" }"#).unwrap();
+        assert_eq!(config.bytes, b"ab");
+        assert_eq!(parse(r##"br#"a"b"#"##), Some(b"a\"b".to_vec()));
+    }
+
+    /// Untagged enums deserialize the literal as any type, so it has to be guessed.
+    #[test]
+    fn guesses_literal_types() {
+        #[derive(Debug, Clone, PartialEq, Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Float(f64),
+            Text(String),
+            Wall { height: u8 },
+        }
+
+        assert_eq!(parse("1.5f32"), Some(Value::Float(1.5)));
+        assert_eq!(parse(r#""a""#), Some(Value::Text("a".to_string())));
+        assert_eq!(
+            parse("brick { height: 2 }"),
+            Some(Value::Wall { height: 2 })
+        );
+        assert_eq!(
+            parse("r#wall { height: 2 }"),
+            Some(Value::Wall { height: 2 })
+        );
+    }
+
+    #[test]
+    fn forwards_nested_errors() {
+        let out_of_range = |literal: &str, ty| TweakError::OutOfRange {
+            literal: literal.to_string(),
+            ty,
+        };
+        assert_eq!(
+            TweakSerde::<Config>::try_parse("Config { cell: [1, 300] }"),
+            Err(out_of_range("300", "u8"))
+        );
+        assert_eq!(
+            TweakSerde::<Difficulty>::try_parse("Difficulty::Hard { enemies: -1 }"),
+            Err(out_of_range("-1", "u32"))
+        );
+        assert_eq!(
+            TweakSerde::<Vec<f32>>::try_parse("vec![1e999]"),
+            Err(out_of_range("1e999", "f32"))
+        );
+        assert!(matches!(
+            TweakSerde::<Difficulty>::try_parse("Difficulty::Hard { }"),
+            Err(TweakError::Invalid { .. })
+        ));
+    }
+}
@@ -0,0 +1,1736 @@
+//! Implementations of [`Tweakable`] for std types.
+//! Values are parsed from the source text of the literal, following the Rust syntax.
+use crate::lexer::{tokenize, TokenKind};
+use crate::{TweakError, Tweakable};
+use core::hash::{BuildHasher, Hash};
+use core::str::FromStr;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::{CStr, CString};
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+/// Unwraps an `Option` in a `try_parse`, returning `Ok(None)` if it is `None`.
+macro_rules! try_some {
+    ($option: expr) => {
+        match $option {
+            Some(v) => v,
+            None => return Ok(None),
+        }
+    };
+}
+
+pub(crate) use try_some;
+
+// Follows reference https://doc.rust-lang.org/reference/tokens.html#floating-point-literals
+// Also supports the constants of the type, like `f32::INFINITY` or `std::f32::consts::PI`.
+macro_rules! impl_tweakable_float {
+    ($($t: ident) +) => {
+        $(
+        impl Tweakable for $t {
+            fn parse(x: &str) -> Option<$t> {
+                Self::try_parse(x).ok().flatten()
+            }
+
+            fn try_parse(x: &str) -> Result<Option<$t>, TweakError> {
+                let ty = stringify!($t);
+                let (negative, v) = match x.trim().strip_prefix('-') {
+                    Some(v) => (true, v.trim_start()),
+                    None => (false, x.trim()),
+                };
+
+                let value = if let Some(constant) = float_constant(v, ty, &[
+                    ("INFINITY", $t::INFINITY),
+                    ("NEG_INFINITY", $t::NEG_INFINITY),
+                    ("NAN", $t::NAN),
+                    ("MAX", $t::MAX),
+                    ("MIN", $t::MIN),
+                    ("MIN_POSITIVE", $t::MIN_POSITIVE),
+                    ("EPSILON", $t::EPSILON),
+                ], &[
+                    ("PI", core::$t::consts::PI),
+                    ("TAU", core::$t::consts::TAU),
+                    ("E", core::$t::consts::E),
+                    ("SQRT_2", core::$t::consts::SQRT_2),
+                    ("FRAC_1_SQRT_2", core::$t::consts::FRAC_1_SQRT_2),
+                    ("FRAC_PI_2", core::$t::consts::FRAC_PI_2),
+                    ("FRAC_PI_3", core::$t::consts::FRAC_PI_3),
+                    ("FRAC_PI_4", core::$t::consts::FRAC_PI_4),
+                    ("FRAC_PI_6", core::$t::consts::FRAC_PI_6),
+                    ("FRAC_PI_8", core::$t::consts::FRAC_PI_8),
+                    ("FRAC_1_PI", core::$t::consts::FRAC_1_PI),
+                    ("FRAC_2_PI", core::$t::consts::FRAC_2_PI),
+                    ("FRAC_2_SQRT_PI", core::$t::consts::FRAC_2_SQRT_PI),
+                    ("LN_2", core::$t::consts::LN_2),
+                    ("LN_10", core::$t::consts::LN_10),
+                    ("LOG2_E", core::$t::consts::LOG2_E),
+                    ("LOG2_10", core::$t::consts::LOG2_10),
+                    ("LOG10_E", core::$t::consts::LOG10_E),
+                    ("LOG10_2", core::$t::consts::LOG10_2),
+                ]) {
+                    constant
+                } else {
+                    let Some((digits, suffix)) = split_float_literal(v) else {
+                        return Ok(None);
+                    };
+                    if suffix.is_some_and(|suffix| suffix != ty) {
+                        return Err(TweakError::WrongSuffix {
+                            literal: x.trim().to_string(),
+                            ty,
+                        });
+                    }
+                    let Ok(value) = <$t>::from_str(&digits) else {
+                        return Ok(None);
+                    };
+                    // Like rustc, a literal too large for the type is an error instead of infinity
+                    if value.is_infinite() {
+                        return Err(TweakError::OutOfRange {
+                            literal: x.trim().to_string(),
+                            ty,
+                        });
+                    }
+                    value
+                };
+
+                Ok(Some(if negative { -value } else { value }))
+            }
+        }
+        )+
+    };
+}
+
+/// Returns the value of the constant at `path`, either an associated constant of the float type `ty`
+/// (e.g `f32::MAX`) or a mathematical constant of its `consts` module (e.g `std::f32::consts::PI`).
+/// The mathematical constants need at least the `consts` module in their path, e.g `consts::PI`,
+/// as a name on its own like `E` could be anything.
+fn float_constant<T: Copy>(
+    path: &str,
+    ty: &str,
+    associated: &[(&str, T)],
+    consts: &[(&str, T)],
+) -> Option<T> {
+    let (prefix, name) = match path.rsplit_once("::") {
+        Some((prefix, name)) => (Some(prefix), name.trim()),
+        None => (None, path),
+    };
+    let find =
+        |constants: &[(&str, T)]| constants.iter().find(|(n, _)| *n == name).map(|(_, v)| *v);
+
+    if let Some(v) = find(associated) {
+        return prefix
+            .is_some_and(|prefix| path_matches(prefix, ty))
+            .then_some(v);
+    }
+
+    let v = find(consts)?;
+    let module = prefix?.trim().strip_suffix("consts")?;
+    let valid = module.is_empty()
+        || module
+            .strip_suffix("::")
+            .is_some_and(|module| path_matches(module, ty));
+    valid.then_some(v)
+}
+
+/// Splits a float literal like `2.5E+4_f32` into the text of its value (without underscores) and its suffix.
+/// Integer literals are accepted as well, and their suffix returned for error reporting.
+fn split_float_literal(x: &str) -> Option<(String, Option<&'static str>)> {
+    let s = x.replace('_', "");
+    let suffix = ["f32", "f64"]
+        .iter()
+        .chain(&IntegerLiteral::SUFFIXES)
+        .find(|&&suffix| s.ends_with(suffix))
+        .copied();
+    let s = &s[..s.len() - suffix.map_or(0, str::len)];
+
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+    let (integer, fraction) = match mantissa.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let valid = is_digits(integer)
+        && match fraction {
+            // `1.` cannot have an exponent or suffix, as `1.e5` would be a field access
+            Some("") => exponent.is_none() && suffix.is_none(),
+            Some(fraction) => is_digits(fraction),
+            None => true,
+        }
+        && exponent.map_or(true, |e| is_digits(e.strip_prefix(['+', '-']).unwrap_or(e)));
+
+    valid.then(|| (s.to_string(), suffix))
+}
+
+// Follows reference https://doc.rust-lang.org/reference/expressions/literal-expr.html
+macro_rules! impl_tweakable_integer {
+    ($($t: ty) +) => {
+        $(
+        impl Tweakable for $t {
+            fn parse(x: &str) -> Option<$t> {
+                Self::try_parse(x).ok().flatten()
+            }
+
+            fn try_parse(x: &str) -> Result<Option<$t>, TweakError> {
+                let ty = stringify!($t);
+                let out_of_range = || TweakError::OutOfRange {
+                    literal: x.trim().to_string(),
+                    ty,
+                };
+
+                // Byte literals like `b'a'` are integers too
+                if let Some(bytes) = parse_char_literal(x, StrKind::Byte) {
+                    let [byte] = bytes[..] else { return Ok(None) };
+                    return <$t>::try_from(byte).map(Some).map_err(|_| out_of_range());
+                }
+
+                let Some(lit) = IntegerLiteral::parse(x) else {
+                    return Ok(None);
+                };
+
+                if lit.suffix.is_some_and(|suffix| suffix != ty) {
+                    return Err(TweakError::WrongSuffix {
+                        literal: x.trim().to_string(),
+                        ty,
+                    });
+                }
+
+                let v = lit.magnitude.and_then(|magnitude| {
+                    if lit.negative {
+                        <$t>::try_from(0i128.checked_sub_unsigned(magnitude)?).ok()
+                    } else {
+                        <$t>::try_from(magnitude).ok()
+                    }
+                });
+
+                v.map(Some).ok_or_else(out_of_range)
+            }
+        }
+        )+
+    };
+}
+
+/// An integer literal, possibly negated, like `-0xFF_u8`.
+pub(crate) struct IntegerLiteral {
+    pub(crate) negative: bool,
+    /// `None` if the value does not even fit in a `u128`.
+    pub(crate) magnitude: Option<u128>,
+    pub(crate) suffix: Option<&'static str>,
+}
+
+impl IntegerLiteral {
+    pub(crate) const SUFFIXES: [&'static str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    pub(crate) fn parse(x: &str) -> Option<Self> {
+        let s = x.trim().replace('_', "");
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s.trim_start()),
+            None => (false, &*s),
+        };
+
+        let (radix, digits) = if let Some(digits) = s.strip_prefix("0x") {
+            (16, digits)
+        } else if let Some(digits) = s.strip_prefix("0o") {
+            (8, digits)
+        } else if let Some(digits) = s.strip_prefix("0b") {
+            (2, digits)
+        } else {
+            (10, s)
+        };
+
+        // Hexadecimal digits may look like a float suffix, e.g `0x1f32`
+        let float_suffixes: &[&'static str] = if radix == 10 { &["f32", "f64"] } else { &[] };
+        let suffix = Self::SUFFIXES
+            .iter()
+            .chain(float_suffixes)
+            .find(|&&suffix| digits.ends_with(suffix))
+            .copied();
+        let digits = &digits[..digits.len() - suffix.map_or(0, str::len)];
+
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return None;
+        }
+
+        Some(IntegerLiteral {
+            negative,
+            magnitude: u128::from_str_radix(digits, radix).ok(),
+            suffix,
+        })
+    }
+}
+
+impl_tweakable_integer!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 usize isize);
+impl_tweakable_float!(f32 f64);
+
+impl Tweakable for bool {
+    fn parse(x: &str) -> Option<Self> {
+        match x {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl Tweakable for char {
+    fn parse(x: &str) -> Option<Self> {
+        let decoded = String::from_utf8(parse_char_literal(x, StrKind::Str)?).ok()?;
+        let mut chars = decoded.chars();
+        let c = chars.next()?;
+        chars.next().is_none().then_some(c)
+    }
+}
+
+/// The parsed strings are interned, so that reloading a file only leaks the strings that were not seen before.
+impl Tweakable for &'static str {
+    fn parse(x: &str) -> Option<Self> {
+        static INTERNED: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+        Some(intern(&INTERNED, owned_str_literal(x)?.into_boxed_str()))
+    }
+}
+
+impl Tweakable for String {
+    fn parse(x: &str) -> Option<Self> {
+        owned_str_literal(x)
+    }
+}
+
+impl Tweakable for Box<str> {
+    fn parse(x: &str) -> Option<Self> {
+        owned_str_literal(x).map(Into::into)
+    }
+}
+
+impl Tweakable for Arc<str> {
+    fn parse(x: &str) -> Option<Self> {
+        owned_str_literal(x).map(Into::into)
+    }
+}
+
+/// Always parsed as `Cow::Owned`, as the source text does not live long enough to be borrowed.
+impl Tweakable for Cow<'static, str> {
+    fn parse(x: &str) -> Option<Self> {
+        owned_str_literal(x).map(Cow::Owned)
+    }
+}
+
+/// Byte string literals like `b"abc"`. The parsed bytes are interned like `&'static str`.
+impl Tweakable for &'static [u8] {
+    fn parse(x: &str) -> Option<Self> {
+        static INTERNED: LazyLock<Mutex<HashSet<&'static [u8]>>> = LazyLock::new(Default::default);
+        let bytes = parse_str_literal(x, StrKind::Byte)?.into_boxed_slice();
+        Some(intern(&INTERNED, bytes))
+    }
+}
+
+/// Byte string literals like `b"abc"`, which are typed as arrays by rustc.
+impl<const N: usize> Tweakable for &'static [u8; N] {
+    fn parse(x: &str) -> Option<Self> {
+        <&[u8]>::parse(x)?.try_into().ok()
+    }
+}
+
+/// C string literals like `c"abc"`. The parsed strings are interned like `&'static str`.
+impl Tweakable for &'static CStr {
+    fn parse(x: &str) -> Option<Self> {
+        static INTERNED: LazyLock<Mutex<HashSet<&'static CStr>>> = LazyLock::new(Default::default);
+        let decoded = CString::new(parse_str_literal(x, StrKind::C)?).ok()?;
+        Some(intern(&INTERNED, decoded.into_boxed_c_str()))
+    }
+}
+
+impl Tweakable for () {
+    fn parse(_x: &str) -> Option<Self> {
+        Some(())
+    }
+}
+
+/// Supports `Some(v)` and `None`, which may also be written with their full path like `Option::None`.
+/// A successfully parsed `None` is returned as `Some(None)`, while a parsing failure returns `None`.
+impl<T: Tweakable> Tweakable for Option<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        if path_matches(x, "None") {
+            return Ok(Some(None));
+        }
+
+        let mut args = try_some!(split_top_level(try_some!(strip_call(x, "Some")))).into_iter();
+        let v = try_some!(T::try_parse(try_some!(args.next()))?);
+        if args.next().is_some() {
+            return Ok(None);
+        }
+        Ok(Some(Some(v)))
+    }
+}
+
+/// Supports `start..end`, e.g `0.0..1.0`.
+impl<T: Tweakable> Tweakable for Range<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        match try_some!(split_range(x)) {
+            (start, end, false) => Ok(Some(
+                try_some!(T::try_parse(start)?)..try_some!(T::try_parse(end)?),
+            )),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Supports `start..=end`, e.g `3..=7`.
+impl<T: Tweakable> Tweakable for RangeInclusive<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        match try_some!(split_range(x)) {
+            (start, end, true) => Ok(Some(
+                try_some!(T::try_parse(start)?)..=try_some!(T::try_parse(end)?),
+            )),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Supports `start..`, e.g `3..`.
+impl<T: Tweakable> Tweakable for RangeFrom<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        match try_some!(split_range(x)) {
+            (start, "", false) => Ok(Some(try_some!(T::try_parse(start)?)..)),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Supports `..end`, e.g `..7`.
+impl<T: Tweakable> Tweakable for RangeTo<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        match try_some!(split_range(x)) {
+            ("", end, false) => Ok(Some(..try_some!(T::try_parse(end)?))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Supports `..=end`, e.g `..=7`.
+impl<T: Tweakable> Tweakable for RangeToInclusive<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        match try_some!(split_range(x)) {
+            ("", end, true) => Ok(Some(..=try_some!(T::try_parse(end)?))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Supports the `Duration` constructors like `Duration::from_millis(200)` or `Duration::from_secs_f32(0.5)`,
+/// and the `Duration::ZERO` and `Duration::MAX` constants.
+impl Tweakable for Duration {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        let x = x.trim();
+        if path_matches(x, "Duration::ZERO") {
+            return Ok(Some(Duration::ZERO));
+        }
+        if path_matches(x, "Duration::MAX") {
+            return Ok(Some(Duration::MAX));
+        }
+
+        let open = try_some!(x.find('('));
+        let constructor = try_some!(x[..open].trim_end().rsplit_once("::"));
+        if !path_matches(constructor.0, "Duration") {
+            return Ok(None);
+        }
+
+        let args = try_some!(strip_delimiters(&x[open..], '(', ')'));
+        let args = try_some!(split_top_level(args));
+        let duration = match (constructor.1.trim(), &args[..]) {
+            ("from_secs", [secs]) => Some(Duration::from_secs(try_some!(u64::try_parse(secs)?))),
+            ("from_millis", [millis]) => {
+                Some(Duration::from_millis(try_some!(u64::try_parse(millis)?)))
+            }
+            ("from_micros", [micros]) => {
+                Some(Duration::from_micros(try_some!(u64::try_parse(micros)?)))
+            }
+            ("from_nanos", [nanos]) => {
+                Some(Duration::from_nanos(try_some!(u64::try_parse(nanos)?)))
+            }
+            ("from_secs_f32", [secs]) => {
+                Duration::try_from_secs_f32(try_some!(f32::try_parse(secs)?)).ok()
+            }
+            ("from_secs_f64", [secs]) => {
+                Duration::try_from_secs_f64(try_some!(f64::try_parse(secs)?)).ok()
+            }
+            ("new", [secs, nanos]) => {
+                let secs = try_some!(u64::try_parse(secs)?);
+                let nanos = try_some!(u32::try_parse(nanos)?);
+                Duration::from_secs(secs).checked_add(Duration::from_nanos(nanos.into()))
+            }
+            _ => return Ok(None),
+        };
+
+        // Negative or too large seconds, and `Duration::new` overflowing
+        duration.map(Some).ok_or_else(|| TweakError::OutOfRange {
+            literal: x.to_string(),
+            ty: "Duration",
+        })
+    }
+}
+
+/// Makes any type implementing `FromStr` tweakable, by parsing the string given to [`TweakFromStr::new`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use inline_tweak::TweakFromStr;
+/// use std::net::IpAddr;
+///
+/// loop {
+///     let addr: IpAddr = inline_tweak::tweak!(TweakFromStr::new("127.0.0.1").unwrap()).0;
+///     println!("{}", addr);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TweakFromStr<T>(pub T);
+
+impl<T: FromStr> TweakFromStr<T> {
+    /// Parses `s` using `FromStr`, returning its error if `s` is not valid.
+    pub fn new(s: &str) -> Result<Self, T::Err> {
+        s.parse().map(TweakFromStr)
+    }
+
+    /// Returns the parsed value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> core::ops::Deref for TweakFromStr<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Supports `TweakFromStr::new("...")` followed by `.unwrap()` or `?`, and a bare string literal.
+/// The `FromStr` errors are reported, so that an invalid string keeps the previous value.
+impl<T> Tweakable for TweakFromStr<T>
+where
+    T: FromStr + Clone + Send + 'static,
+    T::Err: std::fmt::Display,
+{
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        let x = x.trim();
+        let call = x
+            .strip_suffix(".unwrap()")
+            .or_else(|| x.strip_suffix('?'))
+            .map(str::trim_end)
+            .unwrap_or(x);
+        let s = try_some!(owned_str_literal(
+            strip_call(call, "TweakFromStr::new").unwrap_or(call)
+        ));
+        s.parse()
+            .map(|v| Some(TweakFromStr(v)))
+            .map_err(|e: T::Err| TweakError::Invalid {
+                literal: x.to_string(),
+                ty: std::any::type_name::<T>(),
+                reason: e.to_string(),
+            })
+    }
+}
+
+macro_rules! impl_tweakable_tuple {
+    ($($name: ident)+) => {
+        impl<$($name: Tweakable),+> Tweakable for ($($name,)+) {
+            fn parse(x: &str) -> Option<Self> {
+                Self::try_parse(x).ok().flatten()
+            }
+
+            fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+                let inner = try_some!(strip_delimiters(x, '(', ')'));
+                let mut elems = try_some!(split_top_level(inner)).into_iter();
+                let v = ($(try_some!($name::try_parse(try_some!(elems.next()))?),)+);
+                if elems.next().is_some() {
+                    return Ok(None);
+                }
+                Ok(Some(v))
+            }
+        }
+    };
+}
+
+impl_tweakable_tuple!(A);
+impl_tweakable_tuple!(A B);
+impl_tweakable_tuple!(A B C);
+impl_tweakable_tuple!(A B C D);
+impl_tweakable_tuple!(A B C D E);
+impl_tweakable_tuple!(A B C D E F);
+impl_tweakable_tuple!(A B C D E F G);
+impl_tweakable_tuple!(A B C D E F G H);
+impl_tweakable_tuple!(A B C D E F G H I);
+impl_tweakable_tuple!(A B C D E F G H I J);
+impl_tweakable_tuple!(A B C D E F G H I J K);
+impl_tweakable_tuple!(A B C D E F G H I J K L);
+
+/// Supports both the list `[a, b, c]` and the repeat `[a; N]` syntax.
+impl<T: Tweakable, const N: usize> Tweakable for [T; N] {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        Ok(try_some!(parse_list(x, Some(N))?).try_into().ok())
+    }
+}
+
+/// Supports `vec![a, b, c]`, `vec![a; n]`, `Vec::from([a, b, c])`, `Vec::new()` and a bare array literal.
+impl<T: Tweakable> Tweakable for Vec<T> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        let x = x.trim();
+        if let Some(args) = strip_call(x, "Vec::new") {
+            return Ok(args.trim().is_empty().then(Vec::new));
+        }
+
+        let list = strip_call(x, "Vec::from")
+            .or_else(|| x.strip_prefix("vec!"))
+            .unwrap_or(x);
+        parse_list(list, None)
+    }
+}
+
+/// Supports `HashMap::from([(k, v), ...])`, `HashMap::new()` and a bare array literal of `(k, v)` tuples.
+impl<K, V, S> Tweakable for HashMap<K, V, S>
+where
+    K: Tweakable + Eq + Hash,
+    V: Tweakable,
+    S: BuildHasher + Default + Clone + Send + 'static,
+{
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        Ok(parse_entries(x, "HashMap")?.map(|entries| entries.into_iter().collect()))
+    }
+}
+
+/// Supports `BTreeMap::from([(k, v), ...])`, `BTreeMap::new()` and a bare array literal of `(k, v)` tuples.
+impl<K: Tweakable + Ord, V: Tweakable> Tweakable for BTreeMap<K, V> {
+    fn parse(x: &str) -> Option<Self> {
+        Self::try_parse(x).ok().flatten()
+    }
+
+    fn try_parse(x: &str) -> Result<Option<Self>, TweakError> {
+        Ok(parse_entries(x, "BTreeMap")?.map(|entries| entries.into_iter().collect()))
+    }
+}
+
+/// Parses an array literal, either as a list `[a, b, c]` or as a repeat expression `[a; n]`.
+/// The repeat count is checked against the expected `len` if any, before allocating the elements,
+/// so that a mistyped count does not abort the program.
+fn parse_list<T: Tweakable>(x: &str, len: Option<usize>) -> Result<Option<Vec<T>>, TweakError> {
+    let inner = try_some!(strip_delimiters(x, '[', ']'));
+
+    if let Some((elem, count)) = split_repeat(inner) {
+        let count = try_some!(usize::try_parse(count)?);
+        if len.is_some_and(|len| len != count) {
+            return Ok(None);
+        }
+        let elem = try_some!(T::try_parse(elem)?);
+
+        let mut list = Vec::new();
+        if list.try_reserve_exact(count).is_err() {
+            return Ok(None);
+        }
+        list.resize(count, elem);
+        return Ok(Some(list));
+    }
+
+    let mut list = vec![];
+    for elem in try_some!(split_top_level(inner)) {
+        list.push(try_some!(T::try_parse(elem)?));
+    }
+    Ok(Some(list))
+}
+
+/// Parses the `(key, value)` entries of a map named `map`.
+fn parse_entries<K: Tweakable, V: Tweakable>(
+    x: &str,
+    map: &str,
+) -> Result<Option<Vec<(K, V)>>, TweakError> {
+    let x = x.trim();
+    if let Some(args) = strip_call(x, &format!("{map}::new")) {
+        return Ok(args.trim().is_empty().then(Vec::new));
+    }
+
+    parse_list(strip_call(x, &format!("{map}::from")).unwrap_or(x), None)
+}
+
+/// Returns the `&'static` version of `value`, only leaking it if it was not already `interned`.
+fn intern<T: ?Sized + Eq + Hash>(
+    interned: &Mutex<HashSet<&'static T>>,
+    value: Box<T>,
+) -> &'static T {
+    let mut interned = interned.lock().unwrap();
+    if let Some(&v) = interned.get(&*value) {
+        return v;
+    }
+
+    let v = Box::leak(value);
+    interned.insert(v);
+    v
+}
+
+/// Decodes a string literal which may be converted to an owned string,
+/// like `String::from("abc")`, `"abc".to_string()` or `"abc".into()`.
+pub(crate) fn owned_str_literal(x: &str) -> Option<String> {
+    let x = x.trim();
+    if strip_call(x, "String::new").is_some_and(|args| args.trim().is_empty()) {
+        return Some(String::new());
+    }
+
+    let x = [".to_string()", ".to_owned()", ".into()"]
+        .iter()
+        .find_map(|method| x.strip_suffix(method))
+        .unwrap_or(x);
+
+    let x = x
+        .find('(')
+        .filter(|&open| x[..open].trim_end().ends_with("::from"))
+        .and_then(|open| strip_delimiters(&x[open..], '(', ')'))
+        .unwrap_or(x);
+
+    String::from_utf8(parse_str_literal(x, StrKind::Str)?).ok()
+}
+
+/// The kinds of string-like literals, which differ by their prefix and allowed escapes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StrKind {
+    /// `"..."` and `'.'`, decoded as UTF-8 text.
+    Str,
+    /// `b"..."` and `b'.'`, decoded as ASCII bytes.
+    Byte,
+    /// `c"..."`, decoded as UTF-8 text which may also contain arbitrary bytes.
+    C,
+}
+
+impl StrKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            StrKind::Str => "",
+            StrKind::Byte => "b",
+            StrKind::C => "c",
+        }
+    }
+}
+
+/// Decodes a string literal of the given `kind`, either regular `"..."` or raw `r#"..."#`.
+pub(crate) fn parse_str_literal(x: &str, kind: StrKind) -> Option<Vec<u8>> {
+    let x = x.trim().strip_prefix(kind.prefix())?;
+    if let Some(raw) = x.strip_prefix('r') {
+        let body = raw.trim_start_matches('#');
+        let hashes = raw.len() - body.len();
+        let body = body
+            .strip_suffix(&raw[..hashes])?
+            .strip_prefix('"')?
+            .strip_suffix('"')?;
+        if kind == StrKind::Byte && !body.is_ascii() {
+            return None;
+        }
+        // Raw strings are verbatim, except for CRLF line endings which are normalized by rustc
+        return Some(body.replace("\r\n", "\n").into_bytes());
+    }
+
+    unescape(x.strip_prefix('"')?.strip_suffix('"')?, kind)
+}
+
+/// Decodes a char literal of the given `kind`, like `'a'` or `b'a'`.
+fn parse_char_literal(x: &str, kind: StrKind) -> Option<Vec<u8>> {
+    let body = x
+        .trim()
+        .strip_prefix(kind.prefix())?
+        .strip_prefix('\'')?
+        .strip_suffix('\'')?;
+    unescape(body, kind)
+}
+
+/// Decodes the escapes of the inside of a string or char literal,
+/// following <https://doc.rust-lang.org/reference/tokens.html#character-and-string-literals>.
+fn unescape(x: &str, kind: StrKind) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(x.len());
+    let mut it = x.chars().peekable();
+    let mut buf = [0; 4];
+
+    while let Some(c) = it.next() {
+        match c {
+            '\\' => {}
+            // CRLF line endings are normalized to LF by rustc
+            '\r' if it.peek() == Some(&'\n') => continue,
+            _ if kind == StrKind::Byte && !c.is_ascii() => return None,
+            _ => {
+                out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                continue;
+            }
+        }
+
+        let escaped = match it.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '\\' => '\\',
+            '0' => '\0',
+            '\'' => '\'',
+            '"' => '"',
+            'x' => {
+                let digits: String = [it.next()?, it.next()?].into_iter().collect();
+                if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return None;
+                }
+                let v = u8::from_str_radix(&digits, 16).ok()?;
+                // Only byte and C strings can represent non-ASCII bytes
+                if kind == StrKind::Str && v > 0x7F {
+                    return None;
+                }
+                out.push(v);
+                continue;
+            }
+            'u' if kind != StrKind::Byte => {
+                if it.next()? != '{' {
+                    return None;
+                }
+                let mut digits = String::new();
+                loop {
+                    match it.next()? {
+                        '}' => break,
+                        '_' => {}
+                        c => digits.push(c),
+                    }
+                }
+                if digits.is_empty()
+                    || digits.len() > 6
+                    || !digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    return None;
+                }
+                char::from_u32(u32::from_str_radix(&digits, 16).ok()?)?
+            }
+            // Line continuation, skips the line ending and the whitespace of the next line
+            '\n' | '\r' => {
+                while it
+                    .next_if(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+                    .is_some()
+                {}
+                continue;
+            }
+            _ => return None,
+        };
+        out.extend_from_slice(escaped.encode_utf8(&mut buf).as_bytes());
+    }
+
+    Some(out)
+}
+
+/// Calls `f` for every punctuation char of `x`, which is outside of literals and comments,
+/// along with its byte index and the bracket depth before that char.
+/// Returns `None` if a bracket is left unclosed.
+pub(crate) fn scan_top_level(x: &str, mut f: impl FnMut(usize, char, u32) -> bool) -> Option<()> {
+    let mut depth = 0u32;
+
+    for token in tokenize(x) {
+        let TokenKind::Punct(c) = token.kind else {
+            continue;
+        };
+        if matches!(c, ')' | ']' | '}') {
+            depth = depth.checked_sub(1)?;
+        }
+        if f(token.start, c, depth) {
+            return Some(());
+        }
+        if matches!(c, '(' | '[' | '{') {
+            depth += 1;
+        }
+    }
+
+    (depth == 0).then_some(())
+}
+
+/// Returns the text between `open` and its matching `close`, if they surround the whole of `x`.
+pub(crate) fn strip_delimiters(x: &str, open: char, close: char) -> Option<&str> {
+    let x = x.trim();
+    let inner = x.strip_prefix(open)?.strip_suffix(close)?;
+
+    // Make sure the first delimiter is closed by the last one, to reject `(a) + (b)`.
+    let mut closed_early = false;
+    scan_top_level(x, |i, c, depth| {
+        closed_early = depth == 0 && c == close && i != x.len() - close.len_utf8();
+        closed_early
+    })?;
+
+    (!closed_early).then_some(inner)
+}
+
+/// Splits `x` on the commas that are not nested in brackets or literals.
+/// The elements are trimmed and a trailing comma is allowed.
+pub(crate) fn split_top_level(x: &str) -> Option<Vec<&str>> {
+    let mut elems = vec![];
+    let mut start = 0;
+    scan_top_level(x, |i, c, depth| {
+        if c == ',' && depth == 0 {
+            elems.push(x[start..i].trim());
+            start = i + 1;
+        }
+        false
+    })?;
+
+    let last = x[start..].trim();
+    if !last.is_empty() {
+        elems.push(last);
+    }
+
+    if elems.iter().any(|elem| elem.is_empty()) {
+        return None;
+    }
+
+    Some(elems)
+}
+
+/// Returns the arguments of a call to the function at `path` (e.g `Vec::from`).
+/// The function may also be called through a longer path, like `std::vec::Vec::from`.
+pub(crate) fn strip_call<'a>(x: &'a str, path: &str) -> Option<&'a str> {
+    let x = x.trim();
+    let open = x.find('(')?;
+    if !path_matches(&x[..open], path) {
+        return None;
+    }
+
+    strip_delimiters(&x[open..], '(', ')')
+}
+
+/// Whether `x` refers to the item at `path`, either directly or through a longer path.
+pub(crate) fn path_matches(x: &str, path: &str) -> bool {
+    x.trim()
+        .strip_suffix(path)
+        .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with("::"))
+}
+
+/// Splits the inside of an array repeat expression `elem; count`.
+pub(crate) fn split_repeat(x: &str) -> Option<(&str, &str)> {
+    let mut semicolon = None;
+    scan_top_level(x, |i, c, depth| {
+        if c == ';' && depth == 0 {
+            semicolon = Some(i);
+        }
+        semicolon.is_some()
+    })?;
+
+    let i = semicolon?;
+    Some((x[..i].trim(), x[i + 1..].trim()))
+}
+
+/// Splits a range expression like `a..b` or `a..=b` into its trimmed bounds, which may be empty,
+/// and whether it is inclusive.
+fn split_range(x: &str) -> Option<(&str, &str, bool)> {
+    let mut dots = None;
+    scan_top_level(x, |i, _, depth| {
+        if depth == 0 && x[i..].starts_with("..") {
+            dots = Some(i);
+        }
+        dots.is_some()
+    })?;
+
+    let i = dots?;
+    let (end, inclusive) = match x[i + 2..].strip_prefix('=') {
+        Some(end) => (end, true),
+        None => (&x[i + 2..], false),
+    };
+    Some((x[..i].trim(), end.trim(), inclusive))
+}
+
+/// Helpers used by the code generated by `#[derive(Tweakable)]`.
+/// The item being parsed is either the type `ty` or its enum `variant`.
+#[doc(hidden)]
+pub mod derive_helpers {
+    use super::{path_matches, scan_top_level, split_top_level, strip_delimiters};
+    use crate::TweakError;
+    use core::ops::BitOr;
+
+    /// Whether `x` refers to the type `ty` (which can also be called `Self`) or to its `variant`.
+    /// Variants may be referred to without the enum name, e.g when imported with `use Enum::*`.
+    fn names_item(x: &str, ty: &str, variant: Option<&str>) -> bool {
+        let x = x.trim();
+        match variant {
+            None => x == "Self" || path_matches(x, ty),
+            Some(variant) => {
+                x == variant
+                    || x.strip_suffix(variant)
+                        .and_then(|prefix| prefix.strip_suffix("::"))
+                        .is_some_and(|prefix| names_item(prefix, ty, None))
+            }
+        }
+    }
+
+    /// Returns the `(field, value)` pairs of a struct literal like `Name { a: 1, b: 2 }`.
+    /// The base of a struct update like `..Default::default()` is ignored.
+    pub fn named_fields<'a>(
+        x: &'a str,
+        ty: &str,
+        variant: Option<&str>,
+    ) -> Option<Vec<(&'a str, &'a str)>> {
+        let x = x.trim();
+        let open = x.find('{')?;
+        if !names_item(&x[..open], ty, variant) {
+            return None;
+        }
+
+        braced_fields(&x[open..])
+    }
+
+    /// Returns the `(field, value)` pairs of the braced part of a struct literal, like `{ a: 1, b: 2 }`.
+    pub(crate) fn braced_fields(x: &str) -> Option<Vec<(&str, &str)>> {
+        split_top_level(strip_delimiters(x, '{', '}')?)?
+            .into_iter()
+            .filter(|field| !field.starts_with(".."))
+            .map(|field| {
+                let (field, value) = field.split_once(':')?;
+                let field = field.trim();
+                Some((field.strip_prefix("r#").unwrap_or(field), value.trim()))
+            })
+            .collect()
+    }
+
+    /// Returns the value of `field` in the pairs returned by [`named_fields`].
+    pub fn field<'a>(fields: &[(&'a str, &'a str)], field: &str) -> Option<&'a str> {
+        fields.iter().find(|(k, _)| *k == field).map(|(_, v)| *v)
+    }
+
+    /// Returns the fields of a tuple struct literal like `Name(1, 2)`.
+    pub fn tuple_fields<'a>(x: &'a str, ty: &str, variant: Option<&str>) -> Option<Vec<&'a str>> {
+        let x = x.trim();
+        let open = x.find('(')?;
+        if !names_item(&x[..open], ty, variant) {
+            return None;
+        }
+
+        split_top_level(strip_delimiters(&x[open..], '(', ')')?)
+    }
+
+    /// Whether `x` is the unit struct literal `Name`.
+    pub fn is_unit(x: &str, ty: &str, variant: Option<&str>) -> bool {
+        names_item(x, ty, variant)
+    }
+
+    /// Parses the `|`-combined paths to the named `flags` of `ty`, like `Flags::A | Flags::B`.
+    /// A path to `ty` naming another flag is reported, as it cannot be told apart from a typo.
+    /// Used by the implementations generated by `tweakable_flags!`.
+    pub fn flags<T: Clone + BitOr<Output = T>>(
+        x: &str,
+        ty: &'static str,
+        flags: &[(&str, T)],
+    ) -> Result<Option<T>, TweakError> {
+        let literal = x.trim();
+        let x = strip_delimiters(x, '(', ')').unwrap_or(x);
+        // `ty` may be a path to the type
+        let name: String = ty.split_whitespace().collect();
+        let name = name.rsplit("::").next().unwrap_or(&name);
+
+        let mut parts = vec![];
+        let mut start = 0;
+        try_some!(scan_top_level(x, |i, c, depth| {
+            if c == '|' && depth == 0 {
+                parts.push(&x[start..i]);
+                start = i + 1;
+            }
+            false
+        }));
+        parts.push(&x[start..]);
+
+        let mut value: Option<T> = None;
+        for part in parts {
+            // Allows `Flags::empty ()` to match `empty()`
+            let part: String = part.split_whitespace().collect();
+            let (path, flag) = try_some!(part.rsplit_once("::"));
+            if !names_item(path, name, None) {
+                return Ok(None);
+            }
+            let Some((_, v)) = flags.iter().find(|(f, _)| *f == flag) else {
+                return Err(TweakError::Invalid {
+                    literal: literal.to_string(),
+                    ty,
+                    reason: format!("`{}` is not one of its listed flags", flag),
+                });
+            };
+            value = Some(match value {
+                Some(value) => value | v.clone(),
+                None => v.clone(),
+            });
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<T: Tweakable>(x: &str) -> Option<T> {
+        T::parse(x)
+    }
+
+    fn out_of_range(literal: &str, ty: &'static str) -> TweakError {
+        TweakError::OutOfRange {
+            literal: literal.to_string(),
+            ty,
+        }
+    }
+
+    fn wrong_suffix(literal: &str, ty: &'static str) -> TweakError {
+        TweakError::WrongSuffix {
+            literal: literal.to_string(),
+            ty,
+        }
+    }
+
+    #[test]
+    fn reports_integer_errors() {
+        assert_eq!(u8::try_parse("255"), Ok(Some(255)));
+        assert_eq!(u8::try_parse("300"), Err(out_of_range("300", "u8")));
+        assert_eq!(u8::try_parse("-1"), Err(out_of_range("-1", "u8")));
+        assert_eq!(u8::try_parse("5u8"), Ok(Some(5)));
+        assert_eq!(u8::try_parse("5u16"), Err(wrong_suffix("5u16", "u8")));
+        assert_eq!(u8::try_parse("b'a'"), Ok(Some(97)));
+        assert_eq!(u8::try_parse("x + 1"), Ok(None));
+    }
+
+    #[test]
+    fn parses_integer_bounds() {
+        let max = "340282366920938463463374607431768211455";
+        assert_eq!(u128::try_parse(max), Ok(Some(u128::MAX)));
+        let over = "340282366920938463463374607431768211456";
+        assert_eq!(u128::try_parse(over), Err(out_of_range(over, "u128")));
+
+        let min = "-170141183460469231731687303715884105728";
+        assert_eq!(i128::try_parse(min), Ok(Some(i128::MIN)));
+        let under = "-170141183460469231731687303715884105729";
+        assert_eq!(i128::try_parse(under), Err(out_of_range(under, "i128")));
+        let positive = &min[1..];
+        assert_eq!(
+            i128::try_parse(positive),
+            Err(out_of_range(positive, "i128"))
+        );
+    }
+
+    #[test]
+    fn parses_integer_radixes() {
+        // `f32` is made of hexadecimal digits, not a suffix
+        assert_eq!(u32::try_parse("0x1f32"), Ok(Some(0x1f32)));
+        assert_eq!(u32::try_parse("0x1f_u32"), Ok(Some(0x1f)));
+        assert_eq!(i8::try_parse("-0x80"), Ok(Some(i8::MIN)));
+        assert_eq!(u8::try_parse("0o17"), Ok(Some(0o17)));
+        assert_eq!(u8::try_parse("0b1010_1010"), Ok(Some(0b1010_1010)));
+        assert_eq!(u8::try_parse("0b102"), Ok(None));
+        assert_eq!(u32::try_parse("1f32"), Err(wrong_suffix("1f32", "u32")));
+    }
+
+    #[test]
+    fn forwards_nested_errors() {
+        let err = out_of_range("300", "u8");
+        assert_eq!(<(u8, u8)>::try_parse("(300, 1)"), Err(err.clone()));
+        assert_eq!(<[u8; 2]>::try_parse("[300, 1]"), Err(err.clone()));
+        assert_eq!(<[u8; 2]>::try_parse("[300; 2]"), Err(err.clone()));
+        assert_eq!(<Vec<u8>>::try_parse("vec![1, 300]"), Err(err.clone()));
+        assert_eq!(<Option<u8>>::try_parse("Some(300)"), Err(err.clone()));
+        assert_eq!(<Range<u8>>::try_parse("1..300"), Err(err.clone()));
+        assert_eq!(<HashMap<u8, u8>>::try_parse("[(1, 300)]"), Err(err.clone()));
+        assert_eq!(<(u8, u8)>::try_parse("(3, x)"), Ok(None));
+    }
+
+    #[test]
+    fn parses_float_literals() {
+        assert_eq!(f32::try_parse("1."), Ok(Some(1.0)));
+        assert_eq!(f32::try_parse("2.5E+4_f32"), Ok(Some(2.5e4)));
+        assert_eq!(f64::try_parse("1_000.5e-3"), Ok(Some(1.0005)));
+        assert_eq!(f32::try_parse("- 3"), Ok(Some(-3.0)));
+        // `1.e5` would be a field access, and `1.f32` a method call
+        assert_eq!(f32::try_parse("1.e5"), Ok(None));
+        assert_eq!(f32::try_parse("1.f32"), Ok(None));
+        assert_eq!(f32::try_parse(".5"), Ok(None));
+        assert_eq!(f32::try_parse("2.5f64"), Err(wrong_suffix("2.5f64", "f32")));
+        assert_eq!(f32::try_parse("2u8"), Err(wrong_suffix("2u8", "f32")));
+        assert_eq!(f32::try_parse("1e39"), Err(out_of_range("1e39", "f32")));
+        assert_eq!(f64::try_parse("1e39"), Ok(Some(1e39)));
+    }
+
+    #[test]
+    fn parses_float_constants() {
+        assert_eq!(f32::try_parse("f32::MAX"), Ok(Some(f32::MAX)));
+        assert_eq!(
+            f32::try_parse("-std::f32::INFINITY"),
+            Ok(Some(f32::NEG_INFINITY))
+        );
+        assert!(f64::try_parse("f64::NAN").unwrap().unwrap().is_nan());
+        assert_eq!(f32::try_parse("f64::MAX"), Ok(None));
+        assert_eq!(f32::try_parse("MAX"), Ok(None));
+
+        assert_eq!(
+            f32::try_parse("consts::PI"),
+            Ok(Some(core::f32::consts::PI))
+        );
+        assert_eq!(
+            f64::try_parse("std::f64::consts::TAU"),
+            Ok(Some(core::f64::consts::TAU))
+        );
+        assert_eq!(f32::try_parse("core::f64::consts::PI"), Ok(None));
+        // A name on its own could be any constant
+        assert_eq!(f32::try_parse("E"), Ok(None));
+        assert_eq!(f32::try_parse("PI"), Ok(None));
+    }
+
+    #[test]
+    fn splits_outside_of_literals() {
+        assert_eq!(
+            split_top_level(r##"",", ',', r#"),"#, b",", [1, 2], f(3, 4)"##),
+            Some(vec![
+                r#"",""#,
+                "','",
+                r##"r#"),"#"##,
+                r#"b",""#,
+                "[1, 2]",
+                "f(3, 4)"
+            ])
+        );
+        assert_eq!(split_top_level("'\\'', ')'"), Some(vec!["'\\''", "')'"]));
+        assert_eq!(split_top_level("(1, 2"), None);
+        assert_eq!(split_top_level("1, 2)"), None);
+        assert_eq!(split_range("1.0..2.5"), Some(("1.0", "2.5", false)));
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(char::parse(r"'\u{1F600}'"), Some('\u{1F600}'));
+        assert_eq!(char::parse(r"'\u{1F_600}'"), Some('\u{1F600}'));
+        assert_eq!(char::parse(r"'\u{D800}'"), None);
+        assert_eq!(char::parse(r"'\u{1234567}'"), None);
+        assert_eq!(char::parse(r"'\''"), Some('\''));
+        assert_eq!(char::parse("'ab'"), None);
+
+        assert_eq!(
+            String::parse(r#""\x41\t\"\\\0""#).as_deref(),
+            Some("A\t\"\\\0")
+        );
+        // Only ASCII can be written with `\x` in strings, but any byte in byte strings
+        assert_eq!(String::parse(r#""\x80""#), None);
+        assert_eq!(<&[u8]>::parse(r#"b"\x80""#), Some(&[0x80][..]));
+        assert_eq!(String::parse(r#""\q""#), None);
+        assert_eq!(String::parse(r#"r"\n""#).as_deref(), Some(r"\n"));
+    }
+
+    #[test]
+    fn skips_line_continuations() {
+        assert_eq!(String::parse("\"a\\\n    b\"").as_deref(), Some("ab"));
+        assert_eq!(String::parse("\"a\\\r\n\n  \tb\"").as_deref(), Some("ab"));
+        assert_eq!(String::parse("\"a \\\n\"").as_deref(), Some("a "));
+    }
+
+    #[test]
+    fn parses_tuples() {
+        assert_eq!(parse::<(f32, f32)>("(1.0, 2.0)"), Some((1.0, 2.0)));
+        assert_eq!(parse::<(f32, f32)>("( -1.0f32 , 2.0, )"), Some((-1.0, 2.0)));
+        assert_eq!(parse::<(f32,)>("(1.0,)"), Some((1.0,)));
+        assert_eq!(parse::<(f32, f32)>("(1.0) + (2.0)"), None);
+        assert_eq!(parse::<(f32, f32)>("(1.0,, 2.0)"), None);
+        assert_eq!(parse::<(f32, f32)>("(1.0, 2.0, 3.0)"), None);
+        assert_eq!(parse::<(char, char)>(r"('\'', ')')"), Some(('\'', ')')));
+        assert_eq!(
+            parse::<(&str, f32)>(r#"("a,)b", 2.0)"#),
+            Some(("a,)b", 2.0))
+        );
+    }
+
+    #[test]
+    fn parses_arrays() {
+        let colors = "[0.2, 0.4, 0.6, 1.0]";
+        assert_eq!(parse::<[f32; 4]>(colors), Some([0.2, 0.4, 0.6, 1.0]));
+        assert_eq!(parse::<[f32; 4]>("[0.5; 4]"), Some([0.5; 4]));
+        assert_eq!(parse::<[f32; 4]>("[0.5; 3]"), None);
+        assert_eq!(parse::<[f32; 3]>("[0.5; 100000000000]"), None);
+        assert_eq!(parse::<[i32; 3]>("[-0x10, 2i32, -3]"), Some([-16, 2, -3]));
+        assert_eq!(parse::<[f32; 0]>("[]"), Some([]));
+
+        let nested = "([1.0, 2.0], (3u8, ','))";
+        assert_eq!(
+            parse::<([f32; 2], (u8, char))>(nested),
+            Some(([1.0, 2.0], (3, ',')))
+        );
+    }
+
+    #[test]
+    fn parses_vecs() {
+        assert_eq!(parse::<Vec<f32>>("vec![1.0, 2.0]"), Some(vec![1.0, 2.0]));
+        assert_eq!(parse::<Vec<f32>>("vec![1.0; 3]"), Some(vec![1.0; 3]));
+        assert_eq!(parse::<Vec<f32>>("vec![0.5; 100000000000000]"), None);
+        assert_eq!(parse::<Vec<f32>>("[1.0, 2.0,]"), Some(vec![1.0, 2.0]));
+        assert_eq!(parse::<Vec<f32>>("Vec::new()"), Some(vec![]));
+        assert_eq!(
+            parse::<Vec<f32>>("std::vec::Vec::from([3.0])"),
+            Some(vec![3.0])
+        );
+        assert_eq!(parse::<Vec<f32>>("MyVec::from([3.0])"), None);
+        assert_eq!(
+            parse::<Vec<Vec<u8>>>("vec![vec![1], vec![]]"),
+            Some(vec![vec![1], vec![]])
+        );
+    }
+
+    #[test]
+    fn parses_maps() {
+        let speeds = r#"HashMap::from([("walk", 1.0), ("run", 2.5)])"#;
+        let speeds = parse::<HashMap<&str, f32>>(speeds).unwrap();
+        assert_eq!((speeds["walk"], speeds["run"]), (1.0, 2.5));
+
+        let speeds = parse::<BTreeMap<&str, f32>>(r#"[("walk", 1.0), ("run", 2.5),]"#).unwrap();
+        assert_eq!(speeds.len(), 2);
+        assert_eq!(
+            parse::<BTreeMap<u8, u8>>("BTreeMap::new()"),
+            Some(BTreeMap::new())
+        );
+        assert_eq!(parse::<BTreeMap<u8, u8>>("[(1, 2, 3)]"), None);
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(parse::<Option<f32>>("Some(3.0)"), Some(Some(3.0)));
+        assert_eq!(parse::<Option<f32>>("None"), Some(None));
+        assert_eq!(
+            parse::<Option<f32>>(" std::option::Option::None "),
+            Some(None)
+        );
+        assert_eq!(parse::<Option<f32>>("Option::Some(1.0,)"), Some(Some(1.0)));
+        assert_eq!(parse::<Option<f32>>("Some(x)"), None);
+        assert_eq!(parse::<Option<f32>>("NotNone"), None);
+        assert_eq!(parse::<Option<Option<u8>>>("Some(None)"), Some(Some(None)));
+        assert_eq!(
+            parse::<Vec<Option<u8>>>("vec![Some(1), None]"),
+            Some(vec![Some(1), None])
+        );
+    }
+
+    #[test]
+    fn parses_byte_literals() {
+        assert_eq!(parse::<u8>("b'x'"), Some(b'x'));
+        assert_eq!(parse::<u8>(r"b'\xFF'"), Some(0xFF));
+        assert_eq!(parse::<u8>(r"b'\n'"), Some(b'\n'));
+        assert_eq!(parse::<u8>("b'é'"), None);
+        assert_eq!(parse::<u8>("b'ab'"), None);
+        assert_eq!(
+            i8::try_parse(r"b'\xFF'"),
+            Err(out_of_range(r"b'\xFF'", "i8"))
+        );
+
+        let png = r#"b"\x89PNG\r\n""#;
+        assert_eq!(parse::<&[u8]>(png), Some(&b"\x89PNG\r\n"[..]));
+        assert_eq!(parse::<&[u8]>(r##"br#"a\x"#"##), Some(&b"a\\x"[..]));
+        assert_eq!(parse::<&[u8]>(r#"b"\u{41}""#), None);
+        assert_eq!(parse::<&[u8; 3]>(r#"b"abc""#), Some(b"abc"));
+        assert_eq!(parse::<&[u8; 2]>(r#"b"abc""#), None);
+        assert_eq!(parse::<&str>(r#"b"abc""#), None);
+    }
+
+    #[test]
+    fn parses_c_string_literals() {
+        let s = r#"c"hi\xFF\u{e9}""#;
+        assert_eq!(parse::<&CStr>(s), Some(c"hi\xFF\u{e9}"));
+        assert_eq!(parse::<&CStr>(r#"c"h\0i""#), None);
+        assert_eq!(parse::<&CStr>(r#"cr"a\n""#), Some(c"a\\n"));
+    }
+
+    #[test]
+    fn interns_static_strings() {
+        let a = parse::<&str>(r#""interned""#).unwrap();
+        let b = parse::<&str>(r#""interned""#).unwrap();
+        assert!(std::ptr::eq(a, b));
+
+        let a = parse::<&[u8]>(r#"b"interned""#).unwrap();
+        let b = parse::<&[u8; 8]>(r#"b"interned""#).unwrap();
+        assert!(std::ptr::eq(a.as_ptr(), b.as_ptr()));
+    }
+
+    #[test]
+    fn parses_owned_strings() {
+        let s = |s: &str| Some(s.to_string());
+        assert_eq!(parse::<String>(r#"String::from("a\n")"#), s("a\n"));
+        assert_eq!(parse::<String>(r#""a".to_string()"#), s("a"));
+        assert_eq!(parse::<String>(r#""a(b".to_owned()"#), s("a(b"));
+        assert_eq!(parse::<String>(r#""a""#), s("a"));
+        assert_eq!(parse::<String>("String::new()"), s(""));
+        assert_eq!(parse::<String>(r#""a".len()"#), None);
+        assert_eq!(parse::<Box<str>>(r#""a".into()"#), Some("a".into()));
+        assert_eq!(parse::<Arc<str>>(r#"Arc::from("a")"#), Some("a".into()));
+        assert_eq!(
+            parse::<Cow<'static, str>>(r#"Cow::from("a")"#),
+            Some("a".into())
+        );
+        assert_eq!(
+            parse::<Vec<String>>(r#"vec!["a".to_string(), String::from("b")]"#),
+            Some(vec!["a".into(), "b".into()])
+        );
+    }
+
+    #[test]
+    fn parses_ranges() {
+        use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+
+        assert_eq!(parse("0.0..1.0"), Some(0.0f32..1.0));
+        assert_eq!(parse(" -1.5 .. 2.5e1 "), Some(-1.5f32..25.0));
+        assert_eq!(parse("f32::MIN..f32::MAX"), Some(f32::MIN..f32::MAX));
+        assert_eq!(parse("1..2"), Some(1i32..2));
+        assert_eq!(parse("3..=7"), Some(3u8..=7));
+        assert_eq!(parse("3.."), Some(3u8..));
+        assert_eq!(parse("..4"), Some(..4u8));
+        assert_eq!(parse("..=4"), Some(..=4u8));
+        assert_eq!(parse("'.'..'z'"), Some('.'..'z'));
+        assert_eq!(parse(r#""a..b".."c""#), Some("a..b".."c"));
+        assert_eq!(parse("vec![1..2, 3..4]"), Some(vec![1u8..2, 3..4]));
+        assert_eq!(parse("(1, 2)..(3, 4)"), Some((1u8, 2u8)..(3, 4)));
+
+        // Ranges of another kind
+        assert_eq!(parse::<Range<f32>>("0.0..=1.0"), None);
+        assert_eq!(parse::<Range<f32>>("0.0.."), None);
+        assert_eq!(parse::<RangeInclusive<u8>>("3..7"), None);
+        assert_eq!(parse::<RangeInclusive<u8>>("3..="), None);
+        assert_eq!(parse::<RangeFrom<u8>>("3..4"), None);
+        assert_eq!(parse::<RangeTo<u8>>("..=4"), None);
+        assert_eq!(parse::<RangeToInclusive<u8>>("..4"), None);
+
+        assert_eq!(
+            Range::<u8>::try_parse("0..300"),
+            Err(out_of_range("300", "u8"))
+        );
+    }
+
+    #[test]
+    fn parses_durations() {
+        let ms = |ms| Some(Duration::from_millis(ms));
+        assert_eq!(parse("Duration::from_millis(200)"), ms(200));
+        assert_eq!(parse("std::time::Duration::from_secs_f32(0.5)"), ms(500));
+        assert_eq!(parse("Duration::from_secs_f64(0.25)"), ms(250));
+        assert_eq!(parse("Duration::from_micros(1_000)"), ms(1));
+        assert_eq!(parse("Duration::from_secs(2)"), ms(2000));
+        assert_eq!(
+            parse("Duration::from_nanos(5)"),
+            Some(Duration::from_nanos(5))
+        );
+        assert_eq!(parse("Duration::new(1, 5)"), Some(Duration::new(1, 5)));
+        assert_eq!(parse("Duration::ZERO"), Some(Duration::ZERO));
+        assert_eq!(parse("Duration::MAX"), Some(Duration::MAX));
+        assert_eq!(parse::<Duration>("Instant::from_secs(2)"), None);
+        assert_eq!(parse::<Duration>("Duration::from_secs(2, 3)"), None);
+        assert_eq!(parse::<Duration>("from_secs(2)"), None);
+        assert_eq!(Duration::try_parse("Duration::from_secs(x)"), Ok(None));
+    }
+
+    #[test]
+    fn reports_duration_errors() {
+        let millis = "Duration::from_millis(-5)";
+        assert_eq!(Duration::try_parse(millis), Err(out_of_range("-5", "u64")));
+        let nanos = "Duration::new(1, 5_000_000_000)";
+        assert_eq!(
+            Duration::try_parse(nanos),
+            Err(out_of_range("5_000_000_000", "u32"))
+        );
+
+        for x in [
+            "Duration::from_secs_f64(-0.5)",
+            "Duration::new(18446744073709551615, 1_000_000_000)",
+        ] {
+            assert_eq!(Duration::try_parse(x), Err(out_of_range(x, "Duration")));
+        }
+    }
+
+    #[test]
+    fn parses_from_str() {
+        use std::net::IpAddr;
+        use std::path::PathBuf;
+
+        let localhost: IpAddr = "127.0.0.1".parse().unwrap();
+        for x in [
+            r#"TweakFromStr::new("127.0.0.1").unwrap()"#,
+            r#"inline_tweak::TweakFromStr::new("127.0.0.1")?"#,
+            r#""127.0.0.1""#,
+        ] {
+            assert_eq!(parse(x), Some(TweakFromStr(localhost)));
+        }
+        assert_eq!(parse::<TweakFromStr<IpAddr>>("addr()"), None);
+        assert_eq!(parse(r#""a/b""#), Some(TweakFromStr(PathBuf::from("a/b"))));
+        assert_eq!(
+            TweakFromStr::<IpAddr>::new("nope"),
+            Err("nope".parse::<IpAddr>().unwrap_err())
+        );
+
+        let x = r#"TweakFromStr::new("nope").unwrap()"#;
+        assert_eq!(
+            TweakFromStr::<IpAddr>::try_parse(x),
+            Err(TweakError::Invalid {
+                literal: x.to_string(),
+                ty: std::any::type_name::<IpAddr>(),
+                reason: "invalid IP address syntax".to_string(),
+            })
+        );
+    }
+
+    mod flags {
+        use super::parse;
+        use crate::{TweakError, Tweakable};
+
+        bitflags::bitflags! {
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            pub struct Debug: u32 {
+                const WIREFRAME = 1;
+                const NORMALS = 1 << 1;
+                const BOUNDS = 1 << 2;
+            }
+        }
+
+        crate::tweakable_flags!(Debug { WIREFRAME, NORMALS, BOUNDS, empty(), all() });
+
+        mod manual {
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            pub struct Manual(pub u8);
+
+            impl Manual {
+                pub const A: Manual = Manual(1);
+                pub const B: Manual = Manual(2);
+            }
+
+            impl std::ops::BitOr for Manual {
+                type Output = Manual;
+
+                fn bitor(self, other: Manual) -> Manual {
+                    Manual(self.0 | other.0)
+                }
+            }
+        }
+
+        crate::tweakable_flags!(manual::Manual { A, B });
+
+        #[test]
+        fn combines_flags() {
+            assert_eq!(parse("Debug::WIREFRAME"), Some(Debug::WIREFRAME));
+            assert_eq!(
+                parse("Debug::WIREFRAME | Debug::BOUNDS"),
+                Some(Debug::WIREFRAME | Debug::BOUNDS)
+            );
+            assert_eq!(
+                parse("crate::Debug::WIREFRAME|Debug::NORMALS|Self::BOUNDS"),
+                Some(Debug::all())
+            );
+            assert_eq!(
+                parse("(Debug::WIREFRAME | Debug::BOUNDS)"),
+                Some(Debug::WIREFRAME | Debug::BOUNDS)
+            );
+            assert_eq!(parse("Debug::empty ( )"), Some(Debug::empty()));
+            assert_eq!(parse("Debug::all() | Debug::empty()"), Some(Debug::all()));
+            assert_eq!(
+                parse("Manual::A | manual::Manual::B"),
+                Some(manual::Manual(3))
+            );
+        }
+
+        #[test]
+        fn rejects_unknown_flags() {
+            assert_eq!(
+                Debug::try_parse("Debug::WIREFRAME | Debug::OTHER"),
+                Err(TweakError::Invalid {
+                    literal: "Debug::WIREFRAME | Debug::OTHER".to_string(),
+                    ty: "Debug",
+                    reason: "`OTHER` is not one of its listed flags".to_string(),
+                })
+            );
+            assert!(manual::Manual::try_parse("Manual::C").is_err());
+
+            // Not flags of this type
+            for x in [
+                "Other::WIREFRAME",
+                "WIREFRAME",
+                "Debug::WIREFRAME || Debug::BOUNDS",
+                "Debug::WIREFRAME |",
+            ] {
+                assert_eq!(Debug::try_parse(x), Ok(None));
+            }
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    mod derive {
+        use super::parse;
+        use crate::Tweakable;
+
+        #[derive(Debug, Clone, PartialEq, Tweakable)]
+        #[inline_tweak(crate = crate)]
+        struct Vec2 {
+            x: f32,
+            r#y: f32,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Tweakable)]
+        #[inline_tweak(crate = crate)]
+        struct Meters(f32);
+
+        #[derive(Debug, Clone, PartialEq, Tweakable)]
+        #[inline_tweak(crate = crate)]
+        struct Pair<T>(T, T);
+
+        #[derive(Debug, Clone, PartialEq, Tweakable)]
+        #[inline_tweak(crate = crate)]
+        struct Unit;
+
+        #[derive(Debug, Clone, PartialEq, Tweakable)]
+        #[inline_tweak(crate = crate)]
+        struct Nested {
+            a: Vec2,
+            b: [Meters; 2],
+        }
+
+        #[test]
+        fn parses_named_structs() {
+            let v = Some(Vec2 { x: 1.0, y: 2.0 });
+            assert_eq!(parse("Vec2 { x: 1.0, y: 2.0 }"), v);
+            assert_eq!(parse("Vec2 { y: 2.0, x: 1.0, }"), v);
+            assert_eq!(parse("math::Vec2 { x: 1.0, r#y: 2.0 }"), v);
+            assert_eq!(parse("Self { x: 1.0, y: 2.0 }"), v);
+            assert_eq!(parse::<Vec2>("Vec2 { x: 1.0 }"), None);
+            assert_eq!(parse::<Vec2>("Vec2 { x: 1.0, z: 2.0 }"), None);
+
+            let nested = "Nested { a: Vec2 { x: 1.0, y: 2.0 }, b: [Meters(1.0), 2.0] }";
+            assert_eq!(
+                parse(nested),
+                Some(Nested {
+                    a: Vec2 { x: 1.0, y: 2.0 },
+                    b: [Meters(1.0), Meters(2.0)],
+                })
+            );
+        }
+
+        #[test]
+        fn parses_tuple_and_unit_structs() {
+            assert_eq!(parse("Meters(3.5)"), Some(Meters(3.5)));
+            // Newtypes can be written as their inner value
+            assert_eq!(parse("3.5"), Some(Meters(3.5)));
+            assert_eq!(parse("Pair(1, 2)"), Some(Pair(1u8, 2)));
+            assert_eq!(parse::<Pair<u8>>("Pair(1)"), None);
+            assert_eq!(parse::<Pair<u8>>("Pair(1, 300)"), None);
+            assert_eq!(parse("Unit"), Some(Unit));
+            assert_eq!(parse("Self"), Some(Unit));
+        }
+
+        #[test]
+        fn rejects_other_type_names() {
+            assert_eq!(parse::<Vec2>("Vec3 { x: 1.0, y: 2.0 }"), None);
+            assert_eq!(parse::<Meters>("Feet(3.5)"), None);
+            assert_eq!(parse::<Pair<u8>>("Couple(1, 2)"), None);
+            assert_eq!(parse::<Unit>("Other"), None);
+            assert_eq!(parse::<Unit>("MyUnit"), None);
+        }
+
+        #[derive(Debug, Clone, PartialEq, Tweakable)]
+        #[inline_tweak(crate = crate)]
+        enum Mode {
+            Calm,
+            Aggressive,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Tweakable)]
+        #[inline_tweak(crate = crate)]
+        enum Easing<T> {
+            Linear,
+            Cubic(T),
+            Bezier { a: T, b: T },
+            r#Nested(Mode),
+        }
+
+        #[test]
+        fn parses_unit_variants() {
+            assert_eq!(parse("Mode::Aggressive"), Some(Mode::Aggressive));
+            assert_eq!(parse("Self::Calm"), Some(Mode::Calm));
+            assert_eq!(parse("Calm"), Some(Mode::Calm));
+            assert_eq!(parse("crate::game::Mode::Calm"), Some(Mode::Calm));
+            assert_eq!(parse::<Mode>("Other::Calm"), None);
+        }
+
+        #[test]
+        fn parses_data_variants() {
+            assert_eq!(parse("Easing::Cubic(0.3)"), Some(Easing::Cubic(0.3f32)));
+            assert_eq!(parse("Self::Linear"), Some(Easing::<f32>::Linear));
+            assert_eq!(
+                parse("Easing::Bezier { b: 1.0, a: 2.0 }"),
+                Some(Easing::Bezier { a: 2.0f32, b: 1.0 })
+            );
+            assert_eq!(
+                parse("Easing::Nested(Mode::Calm)"),
+                Some(Easing::<f32>::Nested(Mode::Calm))
+            );
+            assert_eq!(parse::<Easing<f32>>("Easing::Cubic"), None);
+            assert_eq!(parse::<Easing<f32>>("Easing::Linear(1.0)"), None);
+            assert_eq!(parse::<Easing<f32>>("Easing::Bezier { a: 1.0 }"), None);
+        }
+
+        #[test]
+        fn forwards_field_errors() {
+            let err = super::out_of_range("300", "u8");
+            assert_eq!(Pair::<u8>::try_parse("Pair(1, 300)"), Err(err.clone()));
+            assert_eq!(
+                Nested::try_parse("Nested { a: Vec2 { x: 1.0, y: 2.0 }, b: [1.0] }"),
+                Ok(None)
+            );
+            assert_eq!(
+                Easing::<u8>::try_parse("Easing::Cubic(300)"),
+                Err(err.clone())
+            );
+            assert_eq!(
+                Easing::<u8>::try_parse("Easing::Bezier { a: 1, b: 300 }"),
+                Err(err.clone())
+            );
+            assert_eq!(Easing::<u8>::try_parse("Easing::Quadratic(300)"), Ok(None));
+        }
+
+        #[test]
+        fn rejects_unknown_variants() {
+            assert_eq!(parse::<Mode>("Mode::Sleepy"), None);
+            assert_eq!(parse::<Mode>("Sleepy"), None);
+            assert_eq!(parse::<Easing<f32>>("Easing::Quadratic(0.3)"), None);
+        }
+    }
+}